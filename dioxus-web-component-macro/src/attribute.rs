@@ -3,6 +3,7 @@
 use std::borrow::Cow;
 use std::fmt::Debug;
 
+use darling::util::Override;
 use darling::FromMeta;
 use heck::ToKebabCase as _;
 use proc_macro2::{Ident, TokenStream};
@@ -10,12 +11,16 @@ use quote::{quote, ToTokens};
 use syn::ext::IdentExt;
 use syn::{Expr, Meta, Type};
 
+use crate::parameter::{model_event_name, model_inner_type, read_only_signal_inner_type};
+use crate::Property;
+
 #[derive(Debug, FromMeta, Default)]
 struct AttributeReceiver {
     name: Option<String>,
     option: Option<bool>,
     initial: Option<Expr>,
     parse: Option<Expr>,
+    model: Option<Override<String>>,
 }
 
 pub(super) struct Attribute {
@@ -25,6 +30,8 @@ pub(super) struct Attribute {
     is_option: Option<bool>,
     initial: Option<Expr>,
     parse: Option<Expr>,
+    model: Option<Override<String>>,
+    read_only_signal: bool,
 }
 
 impl Debug for Attribute {
@@ -36,12 +43,16 @@ impl Debug for Attribute {
             .field("is_option", &self.is_option)
             .field("initial", &self.initial.to_token_stream().to_string())
             .field("parse", &self.parse.to_token_stream().to_string())
+            .field("model", &self.model)
+            .field("read_only_signal", &self.read_only_signal)
             .finish()
     }
 }
 
 impl Attribute {
     pub(super) fn new(ident: Ident, ty: Type) -> Self {
+        let (ty, read_only_signal) =
+            read_only_signal_inner_type(&ty).map_or((ty, false), |inner| (inner, true));
         Self {
             ident,
             ty,
@@ -49,6 +60,8 @@ impl Attribute {
             is_option: None,
             initial: None,
             parse: None,
+            model: None,
+            read_only_signal,
         }
     }
 
@@ -63,6 +76,14 @@ impl Attribute {
             AttributeReceiver::default()
         };
 
+        let (ty, read_only_signal) = if receiver.model.is_some() {
+            (model_inner_type(&ty)?, false)
+        } else if let Some(inner) = read_only_signal_inner_type(&ty) {
+            (inner, true)
+        } else {
+            (ty, false)
+        };
+
         let result = Self {
             ident,
             ty,
@@ -70,13 +91,28 @@ impl Attribute {
             is_option: receiver.option,
             initial: receiver.initial,
             parse: receiver.parse,
+            model: receiver.model,
+            read_only_signal,
         };
         Ok(result)
     }
+
+    /// Share the model configuration with the property of the same parameter
+    pub(super) fn sync_model(&mut self, property: &mut Property) {
+        if let Some(model) = property.model() {
+            if self.model.is_none() {
+                self.model = Some(model.clone());
+                self.ty = property.ty().clone();
+                self.read_only_signal = false;
+            }
+        } else if let Some(model) = &self.model {
+            property.set_model(model.clone(), self.ty.clone());
+        }
+    }
 }
 
 impl Attribute {
-    pub(super) fn name(&self) -> Cow<str> {
+    pub(super) fn name(&self) -> Cow<'_, str> {
         self.name.as_deref().map_or_else(
             || {
                 let name = self.ident.unraw().to_string();
@@ -116,18 +152,67 @@ impl Attribute {
         )
     }
 
+    pub(super) fn model_event(&self) -> Option<String> {
+        self.model
+            .as_ref()
+            .map(|model| model_event_name(model, &self.name()))
+    }
+
     pub(super) fn struct_attribute(&self) -> TokenStream {
         let Self { ident, ty, .. } = &self;
-        quote! {
-            #ident : ::dioxus::prelude::Signal<#ty>
+        if self.model.is_some() {
+            quote! {
+                #ident : ::dioxus_web_component::Model<#ty>
+            }
+        } else {
+            quote! {
+                #ident : ::dioxus::prelude::Signal<#ty>
+            }
         }
     }
 
-    pub(super) fn new_instance(&self) -> TokenStream {
-        let ident = &self.ident;
+    pub(super) fn new_instance(&self, shared: &Ident, property: Option<&Property>) -> TokenStream {
+        let Self { ident, ty, .. } = &self;
         let initial = self.initial();
+        let Some(event) = self.model_event() else {
+            return quote! {
+                let #ident = ::dioxus::prelude::use_signal(|| #initial);
+            };
+        };
+
+        let name = self.name();
+        let attribute_value = if self.option() {
+            quote! {
+                value.as_ref().map(::std::string::ToString::to_string)
+            }
+        } else {
+            quote! {
+                Some(value.to_string())
+            }
+        };
+        let detail = property.map_or_else(
+            || {
+                quote! {
+                    move |_| ::wasm_bindgen::JsValue::from(attribute_value)
+                }
+            },
+            Property::js_detail,
+        );
+
         quote! {
-            let #ident = ::dioxus::prelude::use_signal(|| #initial);
+            let #ident = ::dioxus_web_component::use_model(|| #initial, {
+                let shared = #shared.clone();
+                move |value: #ty| {
+                    let attribute_value: Option<String> = #attribute_value;
+                    shared.reflect_attribute(#name, attribute_value.as_deref());
+                    shared.dispatch_event(
+                        #event,
+                        value,
+                        ::dioxus_web_component::CustomEventOptions::default(),
+                        #detail,
+                    );
+                }
+            });
         }
     }
 
@@ -140,14 +225,14 @@ impl Attribute {
         if self.option() {
             quote! {
                 #name => {
-                    let value = new_value.and_then(#parse);
+                    let value = new_value.as_deref().and_then(#parse);
                     self.#ident.set(value);
                 }
             }
         } else {
             quote! {
                 #name => {
-                    let value = new_value.and_then(#parse).unwrap_or_else(|| #initial);
+                    let value = new_value.as_deref().and_then(#parse).unwrap_or_else(|| #initial);
                     self.#ident.set(value);
                 }
             }
@@ -157,8 +242,18 @@ impl Attribute {
     pub(super) fn rsx_attribute(&self) -> TokenStream {
         let ident = &self.ident;
 
-        quote! {
-            #ident: #ident().clone(),
+        if self.model.is_some() {
+            quote! {
+                #ident: #ident.signal(),
+            }
+        } else if self.read_only_signal {
+            quote! {
+                #ident: { ::dioxus::prelude::ReadOnlySignal::from(#ident) },
+            }
+        } else {
+            quote! {
+                #ident: #ident().clone(),
+            }
         }
     }
 }
@@ -0,0 +1,153 @@
+use darling::{Error, FromMeta};
+use syn::{Expr, ExprArray, Lit};
+
+/// A list of CSS names, e.g. `states = ["checked", "loading"]`
+///
+/// Used by the custom states, see [MDN - CustomStateSet](https://developer.mozilla.org/en-US/docs/Web/API/CustomStateSet),
+/// and the CSS parts, see [MDN - `::part()`](https://developer.mozilla.org/en-US/docs/Web/CSS/::part)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CssNames(Vec<String>);
+
+/// A list of CSS custom properties, e.g. `css_props = ["--plop-color"]`
+///
+/// See [MDN - Custom properties](https://developer.mozilla.org/en-US/docs/Web/CSS/--*)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CssProperties(Vec<String>);
+
+impl CssNames {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &String> {
+        self.0.iter()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl CssProperties {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &String> {
+        self.0.iter()
+    }
+}
+
+impl FromMeta for CssNames {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        parse_names(expr, check_name).map(Self)
+    }
+}
+
+impl FromMeta for CssProperties {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        parse_names(expr, check_property).map(Self)
+    }
+}
+
+fn parse_names(expr: &Expr, check: fn(&str) -> Result<(), String>) -> darling::Result<Vec<String>> {
+    match expr {
+        Expr::Array(ExprArray { elems, .. }) => {
+            let mut errors = Error::accumulator();
+            let names = elems
+                .iter()
+                .filter_map(|elem| errors.handle(parse_name(elem, check)))
+                .collect();
+            errors.finish_with(names)
+        }
+        Expr::Group(group) => parse_names(&group.expr, check),
+        _ => Err(Error::unexpected_expr_type(expr)),
+    }
+}
+
+fn parse_name(expr: &Expr, check: fn(&str) -> Result<(), String>) -> darling::Result<String> {
+    let Expr::Lit(lit) = expr else {
+        return Err(Error::unexpected_expr_type(expr));
+    };
+    let Lit::Str(value) = &lit.lit else {
+        return Err(Error::unexpected_lit_type(&lit.lit).with_span(expr));
+    };
+    let name = value.value();
+    check(&name).map_err(|msg| Error::custom(msg).with_span(expr))?;
+    Ok(name)
+}
+
+/// A CSS identifier, used with the `:state(...)`, or the `::part(...)` pseudo-class
+fn check_name(name: &str) -> Result<(), String> {
+    let Some(start) = name.chars().next() else {
+        return Err("a CSS name cannot be empty".to_string());
+    };
+    if start.is_ascii_digit() {
+        return Err(format!(
+            "a CSS name cannot start with a digit, having \"{name}\""
+        ));
+    }
+    let search = name
+        .chars()
+        .find(|ch| !(ch.is_alphanumeric() || *ch == '-' || *ch == '_'));
+    if let Some(invalid_char) = search {
+        return Err(format!(
+            "invalid char for a CSS name \"{name}\" containing '{invalid_char}'"
+        ));
+    }
+    Ok(())
+}
+
+/// A CSS custom property starts with `--`
+fn check_property(name: &str) -> Result<(), String> {
+    let Some(suffix) = name.strip_prefix("--") else {
+        return Err(format!(
+            "a CSS custom property should start with '--', having \"{name}\""
+        ));
+    };
+    check_name(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::let_assert;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_names() {
+        let_assert!(Ok(expr) = syn::parse_str::<Expr>(r#"["label", "close-button"]"#));
+        let_assert!(Ok(names) = CssNames::from_expr(&expr));
+        assert_eq!(names.0, vec!["label", "close-button"]);
+    }
+
+    #[test]
+    fn should_reject_invalid_names() {
+        let_assert!(Ok(expr) = syn::parse_str::<Expr>(r#"["", "1st", "is loading", 42]"#));
+        let_assert!(Err(error) = CssNames::from_expr(&expr));
+        assert_eq!(error.len(), 4);
+    }
+
+    #[test]
+    fn should_parse_states() {
+        let_assert!(Ok(expr) = syn::parse_str::<Expr>(r#"["checked", "is-loading", "_pending"]"#));
+        let_assert!(Ok(states) = CssNames::from_expr(&expr));
+        assert_eq!(states.0, vec!["checked", "is-loading", "_pending"]);
+    }
+
+    #[test]
+    fn should_reject_invalid_states() {
+        // The `:state(...)` argument is a plain identifier, without the pseudo-class syntax
+        let_assert!(
+            Ok(expr) = syn::parse_str::<Expr>(r#"[":checked", "state(open)", "2x", "is loading"]"#)
+        );
+        let_assert!(Err(error) = CssNames::from_expr(&expr));
+        assert_eq!(error.len(), 4);
+    }
+
+    #[test]
+    fn should_parse_properties() {
+        let_assert!(Ok(expr) = syn::parse_str::<Expr>(r#"["--plop-color", "--plop_size"]"#));
+        let_assert!(Ok(properties) = CssProperties::from_expr(&expr));
+        assert_eq!(properties.0, vec!["--plop-color", "--plop_size"]);
+    }
+
+    #[test]
+    fn should_reject_invalid_properties() {
+        let_assert!(Ok(expr) = syn::parse_str::<Expr>(r#"["color", "--", "--plop color"]"#));
+        let_assert!(Err(error) = CssProperties::from_expr(&expr));
+        assert_eq!(error.len(), 3);
+    }
+}
@@ -88,6 +88,25 @@ impl Event {
         })
     }
 
+    pub(super) fn builder_event(&self) -> TokenStream {
+        let Self {
+            can_bubble,
+            cancelable,
+            ..
+        } = &self;
+        let web_event_name = self.web_event_name();
+
+        quote! {
+            .event(
+                #web_event_name,
+                ::dioxus_web_component::CustomEventOptions {
+                    can_bubble: #can_bubble,
+                    cancelable: #cancelable,
+                },
+            )
+        }
+    }
+
     pub(super) fn rsx_attribute(&self) -> TokenStream {
         let ident = &self.ident;
 
@@ -107,8 +126,7 @@ impl Event {
         let web_event_name = self.web_event_name();
 
         quote! {
-            let #ident = ::dioxus_web_component::custom_event_handler(
-                #shared.event_target().clone(),
+            let #ident = #shared.event_handler(
                 #web_event_name,
                 ::dioxus_web_component::CustomEventOptions {
                     can_bubble: #can_bubble,
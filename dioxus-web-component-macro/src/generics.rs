@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use darling::ast::NestedMeta;
+use darling::{Error, FromMeta};
+use heck::ToUpperCamelCase;
+use proc_macro2::Span;
+use quote::{format_ident, ToTokens as _};
+use syn::visit_mut::VisitMut;
+use syn::{
+    parse_quote, Expr, ExprLit, GenericParam, Generics, Ident, ItemFn, Lit, Meta, Path, Type,
+};
+
+use crate::tag::Tag;
+
+/// A concrete instantiation of a generic component
+///
+/// For example `instantiate(tag = "num-list", T = i64)`
+#[derive(Debug)]
+pub(crate) struct Instantiate {
+    pub tag: Tag,
+    types: Vec<(Ident, Type)>,
+}
+
+impl FromMeta for Instantiate {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = Error::accumulator();
+        let mut tag = None;
+        let mut types = vec![];
+
+        for item in items {
+            let NestedMeta::Meta(meta @ Meta::NameValue(name_value)) = item else {
+                errors.push(Error::unsupported_format(
+                    "expected `tag = ...` or `T = Type`",
+                ));
+                continue;
+            };
+            if name_value.path.is_ident("tag") {
+                tag = errors.handle(Tag::from_meta(meta));
+            } else if let Some(ident) = name_value.path.get_ident() {
+                if let Some(ty) = errors.handle(parse_type(&name_value.value)) {
+                    types.push((ident.clone(), ty));
+                }
+            } else {
+                errors
+                    .push(Error::unknown_field_path(&name_value.path).with_span(&name_value.path));
+            }
+        }
+
+        errors.finish()?;
+
+        let tag = tag.ok_or_else(|| Error::missing_field("tag"))?;
+        Ok(Self { tag, types })
+    }
+}
+
+/// Parse a type like `i64`, or a string literal like `"Vec<i64>"`
+fn parse_type(value: &Expr) -> darling::Result<Type> {
+    let result = if let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit), ..
+    }) = value
+    {
+        lit.parse()
+    } else {
+        syn::parse2(value.to_token_stream())
+    };
+    result.map_err(|err| Error::custom(err).with_span(value))
+}
+
+impl Instantiate {
+    /// Create the concrete function, and the path to the generic component with the concrete types
+    ///
+    /// # Errors
+    ///
+    /// Fail if a generic type is not provided, or if the component has lifetimes or const generics
+    pub(crate) fn concrete(&self, item_fn: &ItemFn) -> Result<(ItemFn, Path), Error> {
+        let generics = &item_fn.sig.generics;
+        let mut replacements = HashMap::new();
+        let mut arguments = vec![];
+        for param in &generics.params {
+            let GenericParam::Type(type_param) = param else {
+                let msg = "only generic types are supported in a web component";
+                return Err(Error::custom(msg).with_span(param));
+            };
+            let ident = &type_param.ident;
+            let Some((_, ty)) = self.types.iter().find(|(name, _)| name == ident) else {
+                let msg = format!("missing the `{ident}` type for the `{}` tag", self.tag);
+                return Err(Error::custom(msg).with_span(ident));
+            };
+            replacements.insert(ident.clone(), ty.clone());
+            arguments.push(ty.clone());
+        }
+        if let Some((ident, _)) = self
+            .types
+            .iter()
+            .find(|(name, _)| !replacements.contains_key(name))
+        {
+            let msg = format!("unknown generic type `{ident}`");
+            return Err(Error::custom(msg).with_span(ident));
+        }
+
+        let name = &item_fn.sig.ident;
+        let component = parse_quote! { #name::<#(#arguments),*> };
+
+        let mut concrete = item_fn.clone();
+        concrete.sig.ident = format_ident!(
+            "{}",
+            self.tag.to_string().to_upper_camel_case(),
+            span = Span::call_site()
+        );
+        concrete.sig.generics = Generics::default();
+        let mut replace = ReplaceGenerics(replacements);
+        for input in &mut concrete.sig.inputs {
+            replace.visit_fn_arg_mut(input);
+        }
+
+        Ok((concrete, component))
+    }
+}
+
+/// Replace the generic types by concrete types
+struct ReplaceGenerics(HashMap<Ident, Type>);
+
+impl VisitMut for ReplaceGenerics {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(type_path) = ty {
+            if type_path.qself.is_none() {
+                if let Some(replacement) = type_path
+                    .path
+                    .get_ident()
+                    .and_then(|ident| self.0.get(ident))
+                {
+                    *ty = replacement.clone();
+                    return;
+                }
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+}
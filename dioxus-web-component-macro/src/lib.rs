@@ -2,10 +2,9 @@
 #![allow(clippy::multiple_crate_versions)]
 
 use proc_macro::TokenStream;
-use syn::ItemFn;
 
 mod web_component;
-pub(crate) use self::web_component::WebComponent;
+pub(crate) use self::web_component::{ComponentFn, WebComponent};
 
 mod parameter;
 pub(crate) use self::parameter::Parameter;
@@ -19,15 +18,27 @@ pub(crate) use self::properties::Property;
 mod event;
 pub(crate) use self::event::Event;
 
+mod pass_through;
+pub(crate) use self::pass_through::PassThrough;
+
 pub(crate) mod tag;
 
+mod css_names;
+pub(crate) use self::css_names::{CssNames, CssProperties};
+
+mod log_level;
+pub(crate) use self::log_level::LogLevel;
+
+mod generics;
+
 #[doc = include_str!("./doc.md")]
 #[proc_macro_attribute]
 pub fn web_component(args: TokenStream, input: TokenStream) -> TokenStream {
-    let item = syn::parse_macro_input!(input as ItemFn);
+    let ComponentFn { item_fn, has_body } = syn::parse_macro_input!(input as ComponentFn);
 
     let mut errors = darling::Error::accumulator();
-    let wc = WebComponent::parse(args.into(), item, &mut errors);
+    let wc = WebComponent::parse(args.into(), item_fn, &mut errors);
+    wc.check_body(has_body, &mut errors);
     let result = wc.generate(&mut errors);
 
     if let Err(err) = errors.finish() {
@@ -69,4 +80,49 @@ mod tests {
         let errors = errors.finish();
         errors.expect("no errors");
     }
+
+    #[test]
+    fn should_parse_model() {
+        let_assert!(Ok(args) = "".parse());
+        let input = "fn MyWebComponent(
+     #[attribute(model)] value: Signal<String>,
+     #[property(model = \"count-input\")] count: Signal<f64>,
+) -> Element {
+    rsx!()
+}";
+        let item = syn::parse_str::<ItemFn>(input).expect("valid rust code");
+
+        let mut errors = darling::Error::accumulator();
+        let wc = WebComponent::parse(args, item, &mut errors);
+
+        let tokens = wc.generate(&mut errors);
+        let syntax_tree = syn::parse_file(&tokens.to_string()).expect("a file");
+        let formatted = prettyplease::unparse(&syntax_tree);
+        insta::assert_snapshot!(formatted);
+
+        let errors = errors.finish();
+        errors.expect("no errors");
+    }
+
+    #[test]
+    fn should_parse_css_api() {
+        let_assert!(
+            Ok(args) = r#"tag = "plop-button", parts = ["label"], css_props = ["--plop-color"], states = ["pressed"]"#.parse()
+        );
+        let input = "fn Button() -> Element {
+    rsx!()
+}";
+        let item = syn::parse_str::<ItemFn>(input).expect("valid rust code");
+
+        let mut errors = darling::Error::accumulator();
+        let wc = WebComponent::parse(args, item, &mut errors);
+
+        let tokens = wc.generate(&mut errors);
+        let syntax_tree = syn::parse_file(&tokens.to_string()).expect("a file");
+        let formatted = prettyplease::unparse(&syntax_tree);
+        insta::assert_snapshot!(formatted);
+
+        let errors = errors.finish();
+        errors.expect("no errors");
+    }
 }
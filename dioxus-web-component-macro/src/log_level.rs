@@ -0,0 +1,64 @@
+use darling::{Error, FromMeta};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+/// A tracing level, e.g. `log_level = "warn"`
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl FromMeta for LogLevel {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "trace" => Ok(Self::Trace),
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => Err(Error::custom(format!(
+                "invalid log level \"{value}\", expected one of \"trace\", \"debug\", \"info\", \"warn\", \"error\""
+            ))),
+        }
+    }
+}
+
+impl ToTokens for LogLevel {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let level = match self {
+            Self::Trace => quote! { TRACE },
+            Self::Debug => quote! { DEBUG },
+            Self::Info => quote! { INFO },
+            Self::Warn => quote! { WARN },
+            Self::Error => quote! { ERROR },
+        };
+        tokens.extend(quote! {
+            ::dioxus::logger::tracing::Level::#level
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+
+    #[test]
+    fn should_parse_level() {
+        let_assert!(Ok(level) = LogLevel::from_string("WARN"));
+        check!(
+            level.to_token_stream().to_string()
+                == ":: dioxus :: logger :: tracing :: Level :: WARN"
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_level() {
+        let_assert!(Err(_) = LogLevel::from_string("verbose"));
+    }
+}
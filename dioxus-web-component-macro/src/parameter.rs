@@ -1,17 +1,21 @@
 use darling::error::Accumulator;
+use darling::util::Override;
+use darling::Error;
 use proc_macro2::TokenStream;
-use quote::ToTokens as _;
+use quote::{quote, ToTokens as _};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{FnArg, Ident, Pat, PatIdent, PatType, Type};
+use syn::{FnArg, GenericArgument, Ident, Pat, PatIdent, PatType, PathArguments, Type};
 
-use crate::{Attribute, Event, Property};
+use crate::{Attribute, Event, PassThrough, Property};
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Parameter {
     Attribute(Attribute, Option<Property>),
     Property(Property),
     Event(Event),
+    PassThrough(PassThrough),
 }
 
 impl Parameter {
@@ -25,19 +29,22 @@ impl Parameter {
 }
 
 impl Parameter {
-    pub fn struct_attribute(&self) -> TokenStream {
+    /// Pass-through parameters are not part of the web component struct
+    pub fn struct_attribute(&self) -> Option<TokenStream> {
         match self {
-            Self::Attribute(attr, _) => attr.struct_attribute(),
-            Self::Property(prop) => prop.struct_attribute(),
-            Self::Event(evt) => evt.struct_attribute(),
+            Self::Attribute(attr, _) => Some(attr.struct_attribute()),
+            Self::Property(prop) => Some(prop.struct_attribute()),
+            Self::Event(evt) => Some(evt.struct_attribute()),
+            Self::PassThrough(_) => None,
         }
     }
 
     pub fn new_instance(&self, shared: &Ident) -> TokenStream {
         match self {
-            Self::Attribute(attr, _) => attr.new_instance(),
-            Self::Property(prop) => prop.new_instance(),
+            Self::Attribute(attr, prop) => attr.new_instance(shared, prop.as_ref()),
+            Self::Property(prop) => prop.new_instance(shared),
             Self::Event(evt) => evt.new_instance(shared),
+            Self::PassThrough(pass) => pass.new_instance(),
         }
     }
 
@@ -46,14 +53,29 @@ impl Parameter {
             Self::Attribute(attr, _) => attr.ident.clone(),
             Self::Property(prop) => prop.ident.clone(),
             Self::Event(evt) => evt.ident.clone(),
+            Self::PassThrough(pass) => pass.ident.clone(),
         }
     }
 
+    /// The custom event declared in the registration, including the model change events
+    pub fn builder_event(&self) -> Option<TokenStream> {
+        let model_event = match self {
+            Self::Attribute(attr, _) => attr.model_event(),
+            Self::Property(prop) => prop.model_event(),
+            Self::Event(evt) => return Some(evt.builder_event()),
+            Self::PassThrough(_) => None,
+        }?;
+        Some(quote! {
+            .event(#model_event, ::dioxus_web_component::CustomEventOptions::default())
+        })
+    }
+
     pub fn rsx_attribute(&self) -> TokenStream {
         match self {
             Self::Attribute(attr, _) => attr.rsx_attribute(),
             Self::Property(prop) => prop.rsx_attribute(),
             Self::Event(evt) => evt.rsx_attribute(),
+            Self::PassThrough(pass) => pass.rsx_attribute(),
         }
     }
 }
@@ -64,6 +86,7 @@ struct ParameterInfo {
     attribute: Option<Attribute>,
     property: Option<Property>,
     event: Option<Event>,
+    pass_through: Option<PassThrough>,
 }
 
 impl ParameterInfo {
@@ -85,6 +108,7 @@ impl ParameterInfo {
             attribute: None,
             property: None,
             event: None,
+            pass_through: None,
         };
 
         attrs.retain(|attr| result.parse_attribute(errors, attr));
@@ -105,6 +129,18 @@ impl ParameterInfo {
             let attribute = Attribute::parse(attr, self.ident.clone(), self.ty.clone());
             self.attribute = errors.handle(attribute);
             false
+        } else if attr.path().is_ident("slot") {
+            let slot = PassThrough::parse_slot(attr, self.ident.clone(), self.ty.clone());
+            self.pass_through = errors.handle(slot);
+            false
+        } else if attr.path().is_ident("context") {
+            let context = PassThrough::parse_context(self.ident.clone(), self.ty.clone());
+            self.pass_through = Some(context);
+            false
+        } else if attr.path().is_ident("skip") {
+            let skip = PassThrough::parse_skip(attr, self.ident.clone(), self.ty.clone());
+            self.pass_through = errors.handle(skip);
+            false
         } else {
             true
         }
@@ -117,18 +153,30 @@ impl ParameterInfo {
             attribute,
             property,
             event,
+            pass_through,
         } = self;
 
+        if let Some(pass_through) = pass_through {
+            return Parameter::PassThrough(pass_through);
+        }
+
         match (attribute, property, event) {
-            (Some(attr), prop, _) => Parameter::Attribute(attr, prop),
+            (Some(mut attr), Some(mut prop), _) => {
+                attr.sync_model(&mut prop);
+                Parameter::Attribute(attr, Some(prop))
+            }
+            (Some(attr), None, _) => Parameter::Attribute(attr, None),
             (None, Some(prop), _) => Parameter::Property(prop),
             (None, None, Some(event)) => Parameter::Event(event),
             (None, None, None) => {
                 let ty_str = ty.to_token_stream().to_string();
                 let is_event =
                     ty_str.starts_with("EventHandler <") || ty_str.starts_with("Callback <");
+                let is_element = ty_str == "Element" || ty_str == "Option < Element >";
                 if is_event {
                     Parameter::Event(Event::new(ident, ty))
+                } else if is_element {
+                    Parameter::PassThrough(PassThrough::slot(ident, ty))
                 } else {
                     Parameter::Attribute(Attribute::new(ident, ty), None)
                 }
@@ -136,3 +184,41 @@ impl ParameterInfo {
         }
     }
 }
+
+/// Extract the `T` of a model `Signal<T>` parameter
+pub(crate) fn model_inner_type(ty: &Type) -> Result<Type, Error> {
+    generic_inner_type(ty, "Signal").ok_or_else(|| {
+        Error::custom("a model parameter should have the `Signal<T>` type").with_span(ty)
+    })
+}
+
+/// The custom event dispatched when the component changes a model
+pub(crate) fn model_event_name(model: &Override<String>, name: &str) -> String {
+    model
+        .as_ref()
+        .explicit()
+        .map_or_else(|| format!("{name}-changed"), Clone::clone)
+}
+
+/// Extract the `T` of a `ReadOnlySignal<T>` parameter
+pub(crate) fn read_only_signal_inner_type(ty: &Type) -> Option<Type> {
+    generic_inner_type(ty, "ReadOnlySignal")
+}
+
+/// Extract the `T` of a `Wrapper<T>` type
+fn generic_inner_type(ty: &Type, wrapper: &str) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(generics) = &segment.arguments else {
+        return None;
+    };
+    let Some(GenericArgument::Type(inner_ty)) = generics.args.first() else {
+        return None;
+    };
+    Some(inner_ty.clone())
+}
@@ -0,0 +1,171 @@
+#![allow(clippy::min_ident_chars)]
+
+use std::fmt::Debug;
+
+use darling::FromMeta;
+use heck::ToKebabCase as _;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{Expr, Meta, Type};
+
+#[derive(Debug, FromMeta, Default)]
+struct SlotReceiver {
+    name: Option<String>,
+}
+
+#[derive(Debug, FromMeta, Default)]
+struct SkipReceiver {
+    initial: Option<Expr>,
+}
+
+/// A Dioxus parameter that is not exposed by the web component
+pub(super) struct PassThrough {
+    pub ident: Ident,
+    ty: Type,
+    kind: PassThroughKind,
+}
+
+enum PassThroughKind {
+    /// Filled with an HTML `<slot>`
+    Slot { name: Option<String> },
+    /// Filled with the Dioxus context
+    Context,
+    /// Filled with the initial value
+    Skip { initial: Option<Box<Expr>> },
+}
+
+impl Debug for PassThrough {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match &self.kind {
+            PassThroughKind::Slot { name } => format!("Slot({name:?})"),
+            PassThroughKind::Context => "Context".to_string(),
+            PassThroughKind::Skip { initial } => {
+                format!("Skip({})", initial.to_token_stream())
+            }
+        };
+        f.debug_struct("PassThrough")
+            .field("ident", &self.ident.to_string())
+            .field("ty", &self.ty.to_token_stream().to_string())
+            .field("kind", &kind)
+            .finish()
+    }
+}
+
+impl PassThrough {
+    pub(super) fn slot(ident: Ident, ty: Type) -> Self {
+        Self {
+            ident,
+            ty,
+            kind: PassThroughKind::Slot { name: None },
+        }
+    }
+
+    pub(super) fn parse_slot(
+        attr: &syn::Attribute,
+        ident: Ident,
+        ty: Type,
+    ) -> Result<Self, darling::Error> {
+        let receiver = if let Meta::List(_) = &attr.meta {
+            SlotReceiver::from_meta(&attr.meta)?
+        } else {
+            SlotReceiver::default()
+        };
+        let kind = PassThroughKind::Slot {
+            name: receiver.name,
+        };
+        Ok(Self { ident, ty, kind })
+    }
+
+    pub(super) fn parse_context(ident: Ident, ty: Type) -> Self {
+        Self {
+            ident,
+            ty,
+            kind: PassThroughKind::Context,
+        }
+    }
+
+    pub(super) fn parse_skip(
+        attr: &syn::Attribute,
+        ident: Ident,
+        ty: Type,
+    ) -> Result<Self, darling::Error> {
+        let receiver = if let Meta::List(_) = &attr.meta {
+            SkipReceiver::from_meta(&attr.meta)?
+        } else {
+            SkipReceiver::default()
+        };
+        let kind = PassThroughKind::Skip {
+            initial: receiver.initial.map(Box::new),
+        };
+        Ok(Self { ident, ty, kind })
+    }
+}
+
+impl PassThrough {
+    fn is_option(&self) -> bool {
+        let ty_str = self.ty.to_token_stream().to_string();
+        ty_str.starts_with("Option <")
+    }
+
+    /// The slot name, `children` is the default slot
+    fn slot_name(&self, name: Option<&String>) -> Option<String> {
+        name.cloned().or_else(|| {
+            let name = self.ident.unraw().to_string();
+            (name != "children").then(|| name.to_kebab_case())
+        })
+    }
+
+    pub(super) fn new_instance(&self) -> TokenStream {
+        let Self { ident, ty, .. } = &self;
+        match &self.kind {
+            PassThroughKind::Slot { name } => {
+                let slot = self.slot_name(name.as_ref()).map_or_else(
+                    || {
+                        quote! {
+                            ::dioxus::prelude::rsx! { slot {} }
+                        }
+                    },
+                    |name| {
+                        quote! {
+                            ::dioxus::prelude::rsx! { slot { name: #name } }
+                        }
+                    },
+                );
+                if self.is_option() {
+                    quote! {
+                        let #ident = Some(#slot);
+                    }
+                } else {
+                    quote! {
+                        let #ident = #slot;
+                    }
+                }
+            }
+            PassThroughKind::Context => quote! {
+                let #ident = ::dioxus::prelude::use_context::<#ty>();
+            },
+            PassThroughKind::Skip { initial } => {
+                let initial = initial.as_ref().map_or_else(
+                    || {
+                        quote! {
+                            ::std::default::Default::default()
+                        }
+                    },
+                    ToTokens::to_token_stream,
+                );
+                quote! {
+                    let #ident: #ty = #initial;
+                }
+            }
+        }
+    }
+
+    pub(super) fn rsx_attribute(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        quote! {
+            #ident,
+        }
+    }
+}
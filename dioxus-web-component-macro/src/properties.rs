@@ -4,6 +4,7 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 
 use darling::error::Accumulator;
+use darling::util::Override;
 use darling::FromMeta;
 use heck::{ToKebabCase, ToLowerCamelCase};
 use proc_macro2::{Ident, TokenStream};
@@ -11,6 +12,8 @@ use quote::{quote, ToTokens};
 use syn::ext::IdentExt;
 use syn::{Expr, GenericArgument, Meta, PathArguments, PathSegment, Type};
 
+use crate::parameter::{model_event_name, model_inner_type, read_only_signal_inner_type};
+
 #[derive(Debug, FromMeta, Default)]
 struct PropertyReceiver {
     name: Option<String>,
@@ -19,6 +22,7 @@ struct PropertyReceiver {
     try_from_js: Option<Expr>,
     try_into_js: Option<Expr>,
     js_type: Option<String>,
+    model: Option<Override<String>>,
 }
 
 #[derive(Clone)]
@@ -31,6 +35,8 @@ pub(super) struct Property {
     try_from_js: Option<Expr>,
     try_into_js: Option<Expr>,
     js_type: Option<String>,
+    model: Option<Override<String>>,
+    read_only_signal: bool,
 }
 
 impl Debug for Property {
@@ -50,6 +56,8 @@ impl Debug for Property {
                 &self.try_into_js.to_token_stream().to_string(),
             )
             .field("js_type", &self.js_type)
+            .field("model", &self.model)
+            .field("read_only_signal", &self.read_only_signal)
             .finish()
     }
 }
@@ -66,6 +74,14 @@ impl Property {
             PropertyReceiver::default()
         };
 
+        let (ty, read_only_signal) = if receiver.model.is_some() {
+            (model_inner_type(&ty)?, false)
+        } else if let Some(inner) = read_only_signal_inner_type(&ty) {
+            (inner, true)
+        } else {
+            (ty, false)
+        };
+
         let result = Self {
             ident,
             ty,
@@ -75,13 +91,35 @@ impl Property {
             try_from_js: receiver.try_from_js,
             try_into_js: receiver.try_into_js,
             js_type: receiver.js_type,
+            model: receiver.model,
+            read_only_signal,
         };
         Ok(result)
     }
+
+    pub(super) fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    pub(super) fn model(&self) -> Option<&Override<String>> {
+        self.model.as_ref()
+    }
+
+    pub(super) fn model_event(&self) -> Option<String> {
+        self.model
+            .as_ref()
+            .map(|model| model_event_name(model, &self.name()))
+    }
+
+    pub(super) fn set_model(&mut self, model: Override<String>, ty: Type) {
+        self.model = Some(model);
+        self.ty = ty;
+        self.read_only_signal = false;
+    }
 }
 
 impl Property {
-    pub(super) fn name(&self) -> Cow<str> {
+    pub(super) fn name(&self) -> Cow<'_, str> {
         self.name.as_deref().map_or_else(
             || {
                 let name = self.ident.unraw().to_string();
@@ -129,27 +167,64 @@ impl Property {
         )
     }
 
-    pub(super) fn new_property(&self) -> TokenStream {
+    pub(super) fn builder_property(&self) -> TokenStream {
         let name = self.js_name();
         let readonly = self.readonly.unwrap_or_default();
 
         quote! {
-            ::dioxus_web_component::Property::new(#name, #readonly)
+            .property(#name, #readonly)
         }
     }
 
     pub(super) fn struct_attribute(&self) -> TokenStream {
         let Self { ident, ty, .. } = &self;
-        quote! {
-            #ident : ::dioxus::prelude::Signal<#ty>
+        if self.model.is_some() {
+            quote! {
+                #ident : ::dioxus_web_component::Model<#ty>
+            }
+        } else {
+            quote! {
+                #ident : ::dioxus::prelude::Signal<#ty>
+            }
         }
     }
 
-    pub(super) fn new_instance(&self) -> TokenStream {
-        let ident = &self.ident;
+    pub(super) fn new_instance(&self, shared: &Ident) -> TokenStream {
+        let Self { ident, ty, .. } = &self;
         let initial = self.initial();
+        let Some(model) = &self.model else {
+            return quote! {
+                let #ident = ::dioxus::prelude::use_signal(|| #initial);
+            };
+        };
+
+        let event = model_event_name(model, &self.name());
+        let detail = self.js_detail();
+
         quote! {
-            let #ident = ::dioxus::prelude::use_signal(|| #initial);
+            let #ident = ::dioxus_web_component::use_model(|| #initial, {
+                let shared = #shared.clone();
+                move |value: #ty| {
+                    shared.dispatch_event(
+                        #event,
+                        value,
+                        ::dioxus_web_component::CustomEventOptions::default(),
+                        #detail,
+                    );
+                }
+            });
+        }
+    }
+
+    /// The closure converting the `value` into the `JsValue` used as custom event detail
+    pub(super) fn js_detail(&self) -> TokenStream {
+        let try_into_js = self.try_into_js_value();
+        quote! {
+            |value| {
+                Ok(value)
+                    .and_then(#try_into_js)
+                    .unwrap_or_else(|_| ::wasm_bindgen::JsValue::undefined())
+            }
         }
     }
 
@@ -167,6 +242,20 @@ impl Property {
         }
     }
 
+    pub(super) fn pattern_set_property_value(&self) -> TokenStream {
+        let Self { ident, ty, .. } = &self;
+        let name = self.name();
+
+        quote! {
+            #name => match value.downcast::<#ty>() {
+                Ok(new_value) => self.#ident.set(*new_value),
+                Err(_) => {
+                    ::dioxus::logger::tracing::warn!("Invalid value type for the {} property", #name);
+                }
+            }
+        }
+    }
+
     pub(super) fn pattern_get_property(&self) -> TokenStream {
         let ident = &self.ident;
         let name = self.name();
@@ -188,8 +277,18 @@ impl Property {
     pub(super) fn rsx_attribute(&self) -> TokenStream {
         let ident = &self.ident;
 
-        quote! {
-            #ident: #ident().clone(),
+        if self.model.is_some() {
+            quote! {
+                #ident: #ident.signal(),
+            }
+        } else if self.read_only_signal {
+            quote! {
+                #ident: { ::dioxus::prelude::ReadOnlySignal::from(#ident) },
+            }
+        } else {
+            quote! {
+                #ident: #ident().clone(),
+            }
         }
     }
 
@@ -249,7 +348,7 @@ Or, disable the typescript generation with `#[web_component(no_typescript = true
 #[allow(clippy::match_same_arms)]
 fn extract_path_segment_js_type(segment: &PathSegment, errors: &mut Accumulator) -> Option<String> {
     let ident = segment.ident.to_string();
-    match ident.to_string().as_str() {
+    match ident.as_str() {
         "bool" => Some("boolean".to_string()),
         "u8" | "u16" | "u32" | "i16" | "i32" | "i64" | "f32" | "f64" => Some("number".to_string()),
         // it's probably better to have a number for usize/isize
@@ -9,16 +9,31 @@ use heck::{ToKebabCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use syn::ext::IdentExt;
-use syn::{Expr, Ident, ItemFn};
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, ItemFn, Path, Signature, Token, Visibility};
 
+use crate::generics::Instantiate;
 use crate::tag::Tag;
-use crate::{Attribute, Parameter, Property};
+use crate::{Attribute, CssNames, CssProperties, LogLevel, Parameter, Property};
 
 #[derive(Debug, Default, FromMeta)]
 struct WebComponentReceiver {
     tag: Option<Tag>,
     style: Option<Expr>,
     no_typescript: Option<bool>,
+    shared_runtime: Option<bool>,
+    role: Option<String>,
+    #[darling(default)]
+    states: CssNames,
+    #[darling(default)]
+    parts: CssNames,
+    #[darling(default)]
+    css_props: CssProperties,
+    log_level: Option<LogLevel>,
+    lifecycle_events: Option<bool>,
+    component: Option<Path>,
+    #[darling(multiple)]
+    instantiate: Vec<Instantiate>,
 }
 impl WebComponentReceiver {
     fn parse(attr_args: TokenStream) -> Result<Self, darling::Error> {
@@ -27,12 +42,55 @@ impl WebComponentReceiver {
     }
 }
 
+/// The annotated function
+///
+/// The body is optional when wrapping an existing component
+pub(crate) struct ComponentFn {
+    pub item_fn: ItemFn,
+    pub has_body: bool,
+}
+
+impl Parse for ComponentFn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse::<Visibility>()?;
+        let sig = input.parse::<Signature>()?;
+        let (block, has_body) = if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+            let block = Block {
+                brace_token: syn::token::Brace::default(),
+                stmts: vec![],
+            };
+            (block, false)
+        } else {
+            (input.parse()?, true)
+        };
+
+        let item_fn = ItemFn {
+            attrs,
+            vis,
+            sig,
+            block: Box::new(block),
+        };
+        Ok(Self { item_fn, has_body })
+    }
+}
+
 pub(crate) struct WebComponent {
     tag: Tag,
     style: Option<Expr>,
     parameters: Vec<Parameter>,
     item_fn: ItemFn,
     no_typescript: Option<bool>,
+    shared_runtime: Option<bool>,
+    role: Option<String>,
+    states: CssNames,
+    parts: CssNames,
+    css_props: CssProperties,
+    log_level: Option<LogLevel>,
+    lifecycle_events: Option<bool>,
+    component: Option<Path>,
+    instances: Vec<WebComponent>,
 }
 
 impl WebComponent {
@@ -45,22 +103,74 @@ impl WebComponent {
             tag,
             style,
             no_typescript,
+            shared_runtime,
+            role,
+            states,
+            parts,
+            css_props,
+            log_level,
+            lifecycle_events,
+            component,
+            instantiate,
         } = errors
             .handle(WebComponentReceiver::parse(attr_args))
             .unwrap_or_default();
 
+        let is_generic = !item_fn.sig.generics.params.is_empty();
+        if is_generic && instantiate.is_empty() {
+            let msg = "a generic component requires at least one `instantiate(tag = ..., T = ...)`";
+            errors.push(Error::custom(msg).with_span(&item_fn.sig.generics));
+        }
+        if !is_generic && !instantiate.is_empty() {
+            let msg = "`instantiate` is only available for a generic component";
+            errors.push(Error::custom(msg).with_span(&item_fn.sig.ident));
+        }
+
         let tag = if let Some(tag) = tag {
+            if is_generic {
+                let msg = "a generic component uses the `tag` of each `instantiate(...)`";
+                errors.push(Error::custom(msg).with_span(&item_fn.sig.ident));
+            }
             tag
         } else {
             let tag = item_fn.sig.ident.unraw().to_string().to_kebab_case();
-            errors
-                .handle_in(|| {
-                    tag.parse()
-                        .map_err(|err| Error::custom(err).with_span(&item_fn.sig.ident))
-                })
-                .unwrap_or(Tag::new(tag))
+            if is_generic {
+                Tag::new(tag)
+            } else {
+                errors
+                    .handle_in(|| {
+                        tag.parse()
+                            .map_err(|err| Error::custom(err).with_span(&item_fn.sig.ident))
+                    })
+                    .unwrap_or(Tag::new(tag))
+            }
         };
 
+        let mut instances = vec![];
+        for instance in &instantiate {
+            let Some((mut concrete_fn, component)) = errors.handle(instance.concrete(&item_fn))
+            else {
+                continue;
+            };
+            let parameters = Parameter::parse(errors, &mut concrete_fn.sig.inputs);
+            instances.push(Self {
+                tag: instance.tag.clone(),
+                style: style.clone(),
+                parameters,
+                item_fn: concrete_fn,
+                no_typescript,
+                shared_runtime,
+                role: role.clone(),
+                states: states.clone(),
+                parts: parts.clone(),
+                css_props: css_props.clone(),
+                log_level,
+                lifecycle_events,
+                component: Some(component),
+                instances: vec![],
+            });
+        }
+
         let parameters = Parameter::parse(errors, &mut item_fn.sig.inputs);
 
         Self {
@@ -69,26 +179,56 @@ impl WebComponent {
             parameters,
             item_fn,
             no_typescript,
+            shared_runtime,
+            role,
+            states,
+            parts,
+            css_props,
+            log_level,
+            lifecycle_events,
+            component,
+            instances,
+        }
+    }
+
+    /// A wrapped component does not have a body, otherwise the body is required
+    pub(crate) fn check_body(&self, has_body: bool, errors: &mut Accumulator) {
+        let ident = &self.item_fn.sig.ident;
+        match (&self.component, has_body) {
+            (Some(component), true) => {
+                let msg = "the function body is not used when wrapping a component, remove it";
+                errors.push(Error::custom(msg).with_span(component));
+            }
+            (None, false) => {
+                let msg =
+                    "missing function body, or use `component = ...` to wrap an existing component";
+                errors.push(Error::custom(msg).with_span(ident));
+            }
+            _ => {}
         }
     }
 
     fn attributes(&self) -> impl Iterator<Item = &Attribute> {
         self.parameters.iter().filter_map(|it| match it {
             Parameter::Attribute(attr, _) => Some(attr),
-            Parameter::Property(_) | Parameter::Event(_) => None,
+            Parameter::Property(_) | Parameter::Event(_) | Parameter::PassThrough(_) => None,
         })
     }
 
     fn properties(&self) -> impl Iterator<Item = &Property> {
         self.parameters.iter().filter_map(|it| match it {
             Parameter::Property(prop) | Parameter::Attribute(_, Some(prop)) => Some(prop),
-            Parameter::Attribute(_, None) | Parameter::Event(_) => None,
+            Parameter::Attribute(_, None) | Parameter::Event(_) | Parameter::PassThrough(_) => None,
         })
     }
 }
 
 impl WebComponent {
     pub fn generate(&self, errors: &mut Accumulator) -> TokenStream {
+        if !self.instances.is_empty() {
+            return self.generate_instances(errors);
+        }
+
         let dioxus_component = self.dioxus_component();
         let register_fn = self.register_fn();
         let web_component = self.web_component();
@@ -106,7 +246,25 @@ impl WebComponent {
         }
     }
 
+    /// A generic component generates the web component of each concrete instance
+    fn generate_instances(&self, errors: &mut Accumulator) -> TokenStream {
+        let dioxus_component = self.dioxus_component();
+        let instances = self
+            .instances
+            .iter()
+            .map(|instance| instance.generate(errors))
+            .collect::<Vec<_>>();
+
+        quote! {
+            #dioxus_component
+            #(#instances)*
+        }
+    }
+
     fn dioxus_component(&self) -> TokenStream {
+        if self.component.is_some() {
+            return quote! {};
+        }
         let item_fn = &self.item_fn;
         quote! {
             #[component]
@@ -119,7 +277,8 @@ impl WebComponent {
         let name = self.item_fn.sig.ident.to_string();
         let fn_name = format_ident!("register_{}", name.to_snake_case());
         let attribute_names = self.attributes().map(|attr| attr.name());
-        let props = self.properties().map(Property::new_property);
+        let props = self.properties().map(Property::builder_property);
+        let events = self.parameters.iter().filter_map(Parameter::builder_event);
         let style = self.style.as_ref().map_or_else(
             || {
                 quote! {
@@ -129,21 +288,67 @@ impl WebComponent {
             quote::ToTokens::to_token_stream,
         );
         let tag = &self.tag.to_string();
+        let shared_runtime = self.shared_runtime.unwrap_or_default();
+        let role = self.role.iter();
+        let states = self.states.iter();
+        let parts = self.parts.iter();
+        let css_props = self.css_props.iter();
+        let log_level = self.log_level.iter();
+        let lifecycle_events = self.lifecycle_events.iter();
         let builder_name = self.builder_name();
 
+        let registration_name = format_ident!("{}_registration", name.to_snake_case());
+        let fn_name_in = format_ident!("{fn_name}_in");
+        let fn_name_as = format_ident!("{fn_name}_as");
+
         let doc = format!("Register the `<{}>` web-component", self.tag);
+        let doc_in = format!(
+            "Register the `<{}>` web-component in a scoped custom element registry",
+            self.tag
+        );
+        let doc_as = format!(
+            "Register the `<{}>` web-component with another tag
+
+# Errors
+
+Fail if the tag is invalid, or already used",
+            self.tag
+        );
 
         quote! {
             #[doc = #doc]
             #visibility fn #fn_name() {
-                let attributes = ::std::vec![
-                    #(#attribute_names.to_string()),*
-                ];
-                let properties = ::std::vec![
-                    #(#props),*
-                ];
+                #registration_name(#tag).register_with_builder(#builder_name);
+            }
+
+            #[doc = #doc_in]
+            #visibility fn #fn_name_in(registry: &::dioxus_web_component::CustomElementRegistry) {
+                #registration_name(#tag)
+                    .custom_element_registry(registry.clone())
+                    .register_with_builder(#builder_name);
+            }
+
+            #[doc = #doc_as]
+            #visibility fn #fn_name_as(tag: &str) -> ::std::result::Result<(), ::dioxus_web_component::RegistrationError> {
+                #registration_name(tag).try_register_with_builder(#builder_name)
+            }
+
+            #[doc(hidden)]
+            #[automatically_derived]
+            fn #registration_name(tag: &str) -> ::dioxus_web_component::WebComponentBuilder {
                 let style = #style;
-                ::dioxus_web_component::register_dioxus_web_component(#tag, attributes, properties, style, #builder_name);
+                ::dioxus_web_component::WebComponentBuilder::new(tag)
+                    #(.attribute(#attribute_names))*
+                    #(#props)*
+                    #(#events)*
+                    .style(style)
+                    .shared_runtime(#shared_runtime)
+                    #(.role(#role))*
+                    #(.state(#states))*
+                    #(.part(#parts))*
+                    #(.css_property(#css_props))*
+                    #(.log_level(#log_level))*
+                    #(.lifecycle_events(#lifecycle_events))*
             }
         }
     }
@@ -152,7 +357,10 @@ impl WebComponent {
         let visibility = &self.item_fn.vis;
         let name = self.web_component_name();
 
-        let attributes = self.parameters.iter().map(Parameter::struct_attribute);
+        let attributes = self
+            .parameters
+            .iter()
+            .filter_map(Parameter::struct_attribute);
 
         let doc = format!(
             "The `{name}` web-component that implement [`::dioxus_web_component::DioxusWebComponent`]",
@@ -177,6 +385,10 @@ impl WebComponent {
             .properties()
             .filter(|prop| !prop.readonly())
             .map(Property::pattern_set_property);
+        let property_set_value = self
+            .properties()
+            .filter(|prop| !prop.readonly())
+            .map(Property::pattern_set_property_value);
         let property_get = self.properties().map(Property::pattern_get_property);
 
         quote! {
@@ -202,6 +414,16 @@ impl WebComponent {
                     }
                 }
 
+                #[allow(clippy::single_match)]
+                fn set_property_value(&mut self, property: &str, value: ::std::boxed::Box<dyn ::std::any::Any + ::std::marker::Send>) {
+                    match property {
+                        #(#property_set_value)*
+                        _ => {
+                            ::dioxus::logger::tracing::warn!("No property {property} to set");
+                        }
+                    }
+                }
+
                 #[allow(clippy::single_match, clippy::redundant_closure)]
                 fn get_property(&mut self, property: &str) -> ::wasm_bindgen::JsValue {
                     match property {
@@ -217,7 +439,10 @@ impl WebComponent {
     }
 
     fn builder_fn(&self) -> TokenStream {
-        let name = &self.item_fn.sig.ident;
+        let name = self.component.as_ref().map_or_else(
+            || self.item_fn.sig.ident.to_token_stream(),
+            ToTokens::to_token_stream,
+        );
         let builder_name = self.builder_name();
         let wc_name = self.web_component_name();
         let instance_name = format_ident!("__{}", wc_name.to_string().to_snake_case());
@@ -229,7 +454,11 @@ impl WebComponent {
             .iter()
             .map(|param| param.new_instance(&shared_name));
 
-        let all_idents = self.parameters.iter().map(Parameter::ident);
+        let all_idents = self
+            .parameters
+            .iter()
+            .filter(|param| !matches!(param, Parameter::PassThrough(_)))
+            .map(Parameter::ident);
 
         let all_rsx_attributes = self.parameters.iter().map(Parameter::rsx_attribute);
 
@@ -246,12 +475,11 @@ impl WebComponent {
                     #(#all_idents),*
                 };
 
+                // The messages are applied in order, a batch is applied before the next render
                 let #coroutine_name = ::dioxus::prelude::use_coroutine(move |mut rx| async move {
                     use ::dioxus_web_component::{StreamExt, DioxusWebComponent};
                     while let Some(message) = rx.next().await {
-                        ::dioxus::prelude::spawn(async move {
-                            #instance_name.handle_message(message);
-                        });
+                        #instance_name.handle_message(message);
                     }
                 });
 
@@ -265,6 +493,13 @@ impl WebComponent {
                     }
                 }
             }
+
+            #[automatically_derived]
+            impl ::dioxus_web_component::WebComponentRoot for #wc_name {
+                fn root() -> ::dioxus::prelude::Element {
+                    #builder_name()
+                }
+            }
         }
     }
 
@@ -299,13 +534,33 @@ impl WebComponent {
             })
             .collect::<Vec<_>>()
             .join("\n");
+        let states = if self.states.is_empty() {
+            String::new()
+        } else {
+            let states = self
+                .states
+                .iter()
+                .map(|state| format!("'{state}'"))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            format!(
+                "
+/** The custom states of `<{tag_name}>`, see the `:state(...)` CSS pseudo-class */
+export type {type_name}State = {states};
+"
+            )
+        };
+
+        let jsdoc = self.typescript_jsdoc();
 
         let definition = format!(
             "
-export type {type_name} = HTMLElement & {{
+{jsdoc}export type {type_name} = HTMLElement & {{
     {properties}
+    /** Resolved after the render of the previous changes */
+    readonly updateComplete: Promise<boolean>;
 }};
-
+{states}
 declare global {{
     interface HTMLElementTagNameMap {{
         '{tag_name}': {type_name};
@@ -320,6 +575,34 @@ declare global {{
     }
 }
 
+impl WebComponent {
+    /// The CSS API documentation, with the custom elements manifest tags
+    ///
+    /// See [Custom Elements Manifest](https://custom-elements-manifest.open-wc.org/analyzer/getting-started/#supported-jsdoc)
+    fn typescript_jsdoc(&self) -> String {
+        let tags = self
+            .parts
+            .iter()
+            .map(|part| format!(" * @csspart {part}"))
+            .chain(
+                self.css_props
+                    .iter()
+                    .map(|prop| format!(" * @cssprop {prop}")),
+            )
+            .chain(
+                self.states
+                    .iter()
+                    .map(|state| format!(" * @cssstate {state}")),
+            )
+            .collect::<Vec<_>>();
+        if tags.is_empty() {
+            return String::new();
+        }
+        let tags = tags.join("\n");
+        format!("/**\n{tags}\n */\n")
+    }
+}
+
 impl Debug for WebComponent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WebComponent")
@@ -328,6 +611,15 @@ impl Debug for WebComponent {
             .field("parameters", &self.parameters)
             .field("item_fn", &self.item_fn.sig.to_token_stream().to_string())
             .field("no_typescript", &self.no_typescript)
+            .field("shared_runtime", &self.shared_runtime)
+            .field("role", &self.role)
+            .field("states", &self.states)
+            .field("parts", &self.parts)
+            .field("css_props", &self.css_props)
+            .field("log_level", &self.log_level)
+            .field("lifecycle_events", &self.lifecycle_events)
+            .field("component", &self.component.to_token_stream().to_string())
+            .field("instances", &self.instances)
             .finish()
     }
 }
@@ -352,4 +644,19 @@ mod tests {
         let_assert!(Err(error) = result);
         insta::assert_debug_snapshot!(error);
     }
+
+    #[test]
+    fn should_parse_instantiate_args() {
+        let_assert!(Ok(args) = r#"instantiate(tag = "num-list", T = i64), instantiate(tag = "text-list", T = "Vec<String>")"#.parse());
+        let result = WebComponentReceiver::parse(args);
+        let_assert!(Ok(receiver) = result);
+        assert_eq!(receiver.instantiate.len(), 2);
+    }
+
+    #[test]
+    fn should_reject_instantiate_without_tag() {
+        let_assert!(Ok(args) = "instantiate(T = i64)".parse());
+        let result = WebComponentReceiver::parse(args);
+        let_assert!(Err(_) = result);
+    }
 }
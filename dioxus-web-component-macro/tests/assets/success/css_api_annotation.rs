@@ -0,0 +1,26 @@
+use dioxus::prelude::*;
+use dioxus_web_component::{export_parts, web_component, InjectedStyle};
+
+fn main() {}
+
+/// An icon
+#[web_component(tag = "plop-icon", parts = ["svg"])]
+pub fn Icon() -> Element {
+    rsx! {
+        svg { "part": "svg" }
+    }
+}
+
+/// A button with a CSS API
+#[web_component(
+    tag = "plop-button",
+    style = InjectedStyle::css("span { color: var(--plop-color, blue); }"),
+    parts = ["label"],
+    css_props = ["--plop-color"],
+)]
+pub fn Button(label: String) -> Element {
+    rsx! {
+        plop-icon { "exportparts": export_parts("plop-icon", Some("icon")) }
+        span { "part": "label", "{label}" }
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+#[web_component(
+    instantiate(tag = "num-list", T = i64),
+    instantiate(tag = "text-list", T = String)
+)]
+fn List<T: Clone + PartialEq + Display + FromStr + 'static>(
+    #[attribute(parse = |value| Some(value.split(',').filter_map(|it| it.parse().ok()).collect()))]
+    items: Vec<T>,
+    selected: Option<T>,
+) -> Element {
+    rsx! {
+        ul {
+            for item in items {
+                li { "{item}" }
+            }
+        }
+        if let Some(selected) = selected {
+            p { "{selected}" }
+        }
+    }
+}
+
+fn _register() {
+    register_num_list();
+    register_text_list();
+}
@@ -0,0 +1,12 @@
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+/// A title dispatching `dx-connected`, `dx-rendered`, and `dx-disconnected`
+#[web_component(tag = "plop-title", lifecycle_events = true)]
+pub fn Title(#[property] title: String) -> Element {
+    rsx! {
+        h1 { "{title}" }
+    }
+}
@@ -0,0 +1,16 @@
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+/// A noisy counter, only the warnings are logged
+#[web_component(tag = "plop-counter", log_level = "warn")]
+pub fn Counter(label: String) -> Element {
+    let mut count = use_signal(|| 0);
+    rsx! {
+        button {
+            onclick: move |_| count += 1,
+            "{label} {count}"
+        }
+    }
+}
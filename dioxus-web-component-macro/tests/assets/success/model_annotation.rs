@@ -0,0 +1,28 @@
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+#[web_component(tag = "plop-input")]
+fn MyInput(
+    #[attribute(model)] mut value: Signal<String>,
+    #[attribute(model = "checked-change")] mut checked: Signal<Option<bool>>,
+    #[property(model)] mut count: Signal<f64>,
+    #[attribute(model)]
+    #[property]
+    mut label: Signal<String>,
+) -> Element {
+    rsx! {
+        input {
+            value: "{value}",
+            oninput: move |evt| value.set(evt.value()),
+        }
+        input {
+            r#type: "checkbox",
+            checked: checked().unwrap_or_default(),
+            onchange: move |evt| checked.set(Some(evt.checked())),
+        }
+        button { onclick: move |_| count += 1.0, "{count}" }
+        button { onclick: move |_| label.set(String::from("clicked")), "{label}" }
+    }
+}
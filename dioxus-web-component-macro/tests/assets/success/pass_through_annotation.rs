@@ -0,0 +1,28 @@
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+#[derive(Clone, PartialEq)]
+struct Theme(String);
+
+#[web_component(tag = "plop-card")]
+fn MyCard(
+    title: ReadOnlySignal<String>,
+    #[property] label: ReadOnlySignal<String>,
+    header: Option<Element>,
+    #[slot(name = "bottom")] footer: Element,
+    #[context] theme: Theme,
+    #[skip(initial = 42)] answer: u32,
+    children: Element,
+) -> Element {
+    rsx! {
+        div { class: "{theme.0}",
+            h1 { "{title} {answer}" }
+            p { "{label}" }
+            {header}
+            {children}
+            {footer}
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use dioxus::prelude::*;
+use dioxus_web_component::{use_aria, web_component, Aria};
+
+fn main() {}
+
+/// A toggle button
+#[web_component(tag = "plop-toggle", role = "button")]
+pub fn Toggle(label: String) -> Element {
+    let mut pressed = use_signal(|| false);
+    use_aria(move || Aria::new().pressed(pressed()).label(label.clone()));
+    rsx! {
+        span {
+            onclick: move |_| pressed.toggle(),
+            "Toggle"
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+/// A cell rendered with the shared runtime
+#[web_component(tag = "plop-cell", shared_runtime = true)]
+pub fn Cell(value: String, #[property] title: String, on_select: EventHandler<String>) -> Element {
+    rsx! {
+        span {
+            title,
+            onclick: move |_| on_select(value.clone()),
+            "{value}"
+        }
+    }
+}
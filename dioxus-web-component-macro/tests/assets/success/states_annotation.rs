@@ -0,0 +1,18 @@
+use dioxus::prelude::*;
+use dioxus_web_component::{use_custom_state, web_component};
+
+fn main() {}
+
+/// A toggle with custom states
+#[web_component(tag = "plop-toggle", states = ["checked", "loading"])]
+pub fn Toggle(loading: ReadOnlySignal<bool>) -> Element {
+    let mut checked = use_signal(|| false);
+    use_custom_state("checked", checked);
+    use_custom_state("loading", loading);
+    rsx! {
+        span {
+            onclick: move |_| checked.toggle(),
+            "Toggle"
+        }
+    }
+}
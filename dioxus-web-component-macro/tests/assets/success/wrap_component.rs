@@ -0,0 +1,26 @@
+use dioxus::prelude::*;
+use dioxus_web_component::web_component;
+
+fn main() {}
+
+mod ui {
+    use dioxus::prelude::*;
+
+    #[component]
+    pub fn Button(label: String, on_click: EventHandler<i32>, children: Element) -> Element {
+        rsx! {
+            button { onclick: move |_| on_click(1), "{label}" }
+            {children}
+        }
+    }
+}
+
+/// The `ui::Button` as a web component
+#[web_component(tag = "ui-button", component = ui::Button)]
+pub fn UiButton(
+    #[attribute]
+    #[property]
+    label: String,
+    on_click: EventHandler<i32>,
+    children: Element,
+);
@@ -0,0 +1,128 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::{check_tag, InvalidTagError};
+
+/// A valid custom element name, e.g. `plop-counter`
+///
+/// See [WHATWG - Valid custom element name](https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name)
+///
+/// ```rust
+/// use dioxus_web_component_tag::CustomElementName;
+///
+/// let name = CustomElementName::parse("plop-counter").unwrap();
+/// assert_eq!(name.as_str(), "plop-counter");
+///
+/// let result = "PlopCounter".parse::<CustomElementName>();
+/// assert!(result.is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CustomElementName(String);
+
+impl CustomElementName {
+    /// Parse a custom element name
+    ///
+    /// # Errors
+    ///
+    /// Fail if the name is invalid.
+    pub fn parse(name: impl Into<String>) -> Result<Self, InvalidTagError> {
+        let name = name.into();
+        check_tag(&name)?;
+        Ok(Self(name))
+    }
+
+    /// The name
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for CustomElementName {
+    type Err = InvalidTagError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for CustomElementName {
+    type Error = InvalidTagError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<&str> for CustomElementName {
+    type Error = InvalidTagError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<CustomElementName> for String {
+    fn from(value: CustomElementName) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<str> for CustomElementName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for CustomElementName {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CustomElementName {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CustomElementName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CustomElementName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Self::parse(name).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+
+    #[test]
+    fn should_parse_name() {
+        let_assert!(Ok(name) = "plop-counter".parse::<CustomElementName>());
+        check!(name.to_string() == "plop-counter");
+    }
+
+    #[test]
+    fn should_reject_invalid_name() {
+        let result = CustomElementName::parse("plop counter");
+        let_assert!(Err(InvalidTagError::NoHyphen(_)) = result);
+    }
+}
@@ -0,0 +1,205 @@
+#![doc = include_str!("../README.md")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::{Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, CustomEvent, Document, Element, Event, HtmlElement};
+
+pub use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+fn document() -> Document {
+    window().unwrap_throw().document().unwrap_throw()
+}
+
+/// Mount an HTML fragment in the document body
+///
+/// The web components of the fragment should be registered before.
+/// The fragment is removed from the document when the [`Mounted`] is dropped.
+///
+/// # Panics
+///
+/// Panic if the fragment does not contain an HTML element.
+#[must_use]
+pub fn mount(html: &str) -> Mounted {
+    let document = document();
+    let container = document
+        .create_element("div")
+        .unwrap_throw()
+        .unchecked_into::<HtmlElement>();
+    container.set_inner_html(html);
+    document
+        .body()
+        .unwrap_throw()
+        .append_child(&container)
+        .unwrap_throw();
+
+    let element = container
+        .first_element_child()
+        .expect_throw("the mounted HTML should contain an element")
+        .unchecked_into::<HtmlElement>();
+
+    Mounted { container, element }
+}
+
+/// Wait until the pending renders are done
+///
+/// Call it after mounting the web component, or after changing an attribute or a property.
+pub async fn await_render() {
+    // Dioxus renders asynchronously, after the messages are handled
+    let frame = Promise::new(&mut |resolve, _reject| {
+        let _ = window().unwrap_throw().request_animation_frame(&resolve);
+    });
+    let _ = JsFuture::from(frame).await;
+    let timeout = Promise::new(&mut |resolve, _reject| {
+        let _ = window().unwrap_throw().set_timeout_with_callback(&resolve);
+    });
+    let _ = JsFuture::from(timeout).await;
+}
+
+/// A mounted HTML fragment, see [`mount`]
+#[derive(Debug)]
+pub struct Mounted {
+    container: HtmlElement,
+    element: HtmlElement,
+}
+
+impl Mounted {
+    /// The first element of the mounted fragment, e.g. the web component host
+    #[must_use]
+    pub fn element(&self) -> &HtmlElement {
+        &self.element
+    }
+
+    /// Set an HTML attribute of the element
+    pub fn set_attribute(&self, name: &str, value: &str) {
+        self.element.set_attribute(name, value).unwrap_throw();
+    }
+
+    /// Remove an HTML attribute of the element
+    pub fn remove_attribute(&self, name: &str) {
+        self.element.remove_attribute(name).unwrap_throw();
+    }
+
+    /// Set a Javascript property of the element
+    pub fn set_property(&self, name: &str, value: impl Into<JsValue>) {
+        Reflect::set(&self.element, &name.into(), &value.into()).unwrap_throw();
+    }
+
+    /// Get a Javascript property of the element
+    ///
+    /// The properties of a web component are read asynchronously,
+    /// so a `Promise` value is awaited.
+    pub async fn property(&self, name: &str) -> JsValue {
+        let value = Reflect::get(&self.element, &name.into()).unwrap_throw();
+        match value.dyn_into::<Promise>() {
+            Ok(promise) => JsFuture::from(promise).await.unwrap_throw(),
+            Err(value) => value,
+        }
+    }
+
+    /// Find an element rendered by the web component
+    ///
+    /// The search is done in the shadow root, or in the element if there is no shadow root
+    /// (e.g. with the shared runtime).
+    #[must_use]
+    pub fn query_selector(&self, selector: &str) -> Option<Element> {
+        let result = match self.element.shadow_root() {
+            Some(root) => root.query_selector(selector),
+            None => self.element.query_selector(selector),
+        };
+        result.unwrap_throw()
+    }
+
+    /// The text content rendered by the web component
+    #[must_use]
+    pub fn text_content(&self) -> String {
+        let text = match self.element.shadow_root() {
+            Some(root) => root.text_content(),
+            None => self.element.text_content(),
+        };
+        text.unwrap_or_default()
+    }
+
+    /// Collect the events dispatched by the element
+    #[must_use]
+    pub fn collect_events(&self, event_type: &str) -> EventCollector {
+        EventCollector::new(&self.element, event_type)
+    }
+}
+
+impl Drop for Mounted {
+    fn drop(&mut self) {
+        self.container.remove();
+    }
+}
+
+/// The events dispatched by an element, see [`Mounted::collect_events`]
+///
+/// The listener is removed when the collector is dropped.
+pub struct EventCollector {
+    target: HtmlElement,
+    event_type: String,
+    events: Rc<RefCell<Vec<Event>>>,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl EventCollector {
+    fn new(target: &HtmlElement, event_type: &str) -> Self {
+        let events = Rc::<RefCell<Vec<Event>>>::default();
+        let listener = Closure::<dyn Fn(Event)>::new({
+            let events = Rc::clone(&events);
+            move |event: Event| events.borrow_mut().push(event)
+        });
+        target
+            .add_event_listener_with_callback(event_type, listener.as_ref().unchecked_ref())
+            .unwrap_throw();
+
+        Self {
+            target: target.clone(),
+            event_type: event_type.to_string(),
+            events,
+            listener,
+        }
+    }
+
+    /// The collected events
+    #[must_use]
+    pub fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    /// The details of the collected custom events
+    #[must_use]
+    pub fn details(&self) -> Vec<JsValue> {
+        self.events
+            .borrow()
+            .iter()
+            .filter_map(|event| event.dyn_ref::<CustomEvent>())
+            .map(CustomEvent::detail)
+            .collect()
+    }
+
+    /// The number of collected events
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.borrow().len()
+    }
+
+    /// No event collected
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.borrow().is_empty()
+    }
+}
+
+impl Drop for EventCollector {
+    fn drop(&mut self) {
+        let _ = self.target.remove_event_listener_with_callback(
+            &self.event_type,
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
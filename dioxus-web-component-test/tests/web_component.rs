@@ -0,0 +1,489 @@
+#![allow(missing_docs, clippy::expect_used)]
+#![cfg(target_arch = "wasm32")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_web_component::{
+    provide_dom_context, use_aria, use_custom_state, use_dom_context, web_component, Aria,
+};
+use dioxus_web_component_test::{
+    await_render, mount, wasm_bindgen_test, wasm_bindgen_test_configure, Mounted,
+};
+use js_sys::{Array, Function, Promise, Reflect};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, Event, HtmlElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[web_component(tag = "plop-test-counter")]
+fn Counter(label: String, #[property] step: f64, on_count: EventHandler<f64>) -> Element {
+    let mut counter = use_signal(|| 0.0);
+    rsx! {
+        span { "{label}" }
+        button {
+            onclick: move |_| {
+                counter += step;
+                on_count(counter());
+            },
+            "+"
+        }
+    }
+}
+
+thread_local! {
+    static RENDERED_POINTS: RefCell<Vec<(f64, f64)>> = const { RefCell::new(Vec::new()) };
+}
+
+#[web_component(tag = "plop-test-point")]
+fn Point(#[property] x: f64, #[property] y: f64) -> Element {
+    RENDERED_POINTS.with_borrow_mut(|points| points.push((x, y)));
+    rsx! {
+        span { "{x},{y}" }
+    }
+}
+
+#[web_component(tag = "plop-test-title")]
+fn Title(#[property] title: String) -> Element {
+    rsx! {
+        h1 { "{title}" }
+    }
+}
+
+#[web_component(tag = "plop-test-lifecycle", lifecycle_events = true)]
+fn Lifecycle(#[property] title: String) -> Element {
+    rsx! {
+        h1 { "{title}" }
+    }
+}
+
+#[web_component(tag = "plop-test-cell", shared_runtime = true)]
+fn Cell(label: String) -> Element {
+    rsx! {
+        span { "{label}" }
+    }
+}
+
+#[web_component(tag = "plop-test-theme-provider")]
+fn ThemeProvider(theme: ReadOnlySignal<String>) -> Element {
+    provide_dom_context("test-theme", theme);
+    rsx! { slot {} }
+}
+
+/// Only registered after the consumers are connected
+#[web_component(tag = "plop-test-late-theme-provider")]
+fn LateThemeProvider(theme: ReadOnlySignal<String>) -> Element {
+    provide_dom_context("test-theme", theme);
+    rsx! { slot {} }
+}
+
+#[web_component(tag = "plop-test-theme-consumer")]
+fn ThemeConsumer() -> Element {
+    let theme = use_dom_context::<String>("test-theme");
+    rsx! {
+        span { {theme().unwrap_or_default()} }
+    }
+}
+
+#[web_component(tag = "plop-test-aria", role = "switch")]
+fn AriaSwitch(checked: ReadOnlySignal<bool>) -> Element {
+    use_aria(move || {
+        let aria = Aria::new().checked(checked());
+        // Not an `ElementInternals` property, so the host attribute is used
+        if checked() {
+            aria.label("On").set("ariaPlop", "on")
+        } else {
+            aria
+        }
+    });
+    rsx! { span { "switch" } }
+}
+
+#[web_component(tag = "plop-test-state", states = ["checked"])]
+fn StateToggle(checked: ReadOnlySignal<bool>) -> Element {
+    use_custom_state("checked", checked);
+    rsx! { span { "toggle" } }
+}
+
+#[wasm_bindgen_test]
+async fn should_render_attribute() {
+    register_counter();
+    let counter = mount("<plop-test-counter label='plop'></plop-test-counter>");
+    await_render().await;
+    assert_eq!(counter.text_content(), "plop+");
+
+    counter.set_attribute("label", "plaf");
+    await_render().await;
+    assert_eq!(counter.text_content(), "plaf+");
+}
+
+#[wasm_bindgen_test]
+async fn should_dispatch_events() {
+    register_counter();
+    let counter = mount("<plop-test-counter></plop-test-counter>");
+    let events = counter.collect_events("count");
+    counter.set_property("step", 2.0);
+    await_render().await;
+    assert_eq!(counter.property("step").await, JsValue::from(2.0));
+
+    let button = counter.query_selector("button").expect("a button");
+    button.unchecked_ref::<HtmlElement>().click();
+    button.unchecked_ref::<HtmlElement>().click();
+    await_render().await;
+    assert_eq!(
+        events.details(),
+        vec![JsValue::from(2.0), JsValue::from(4.0)]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_inspect_instances_with_devtools() {
+    register_counter();
+    let counter = mount("<plop-test-counter label='plop'></plop-test-counter>");
+    counter.set_property("step", 3.0);
+    await_render().await;
+
+    let window = window().expect("a window");
+    let devtools = Reflect::get(&window, &"__DIOXUS_WC__".into()).expect("the devtools API");
+    let instances = Reflect::get(&devtools, &"instances".into()).expect("an instances function");
+    let instances = instances
+        .unchecked_ref::<Function>()
+        .call0(&devtools)
+        .expect("a promise");
+    let instances = JsFuture::from(instances.unchecked_into::<Promise>())
+        .await
+        .expect("the instances");
+    let instance = Array::from(&instances)
+        .iter()
+        .find(|instance| {
+            Reflect::get(instance, &"host".into()).ok().as_ref() == Some(counter.element().as_ref())
+        })
+        .expect("the counter instance");
+
+    let attributes = Reflect::get(&instance, &"attributes".into()).expect("attributes");
+    assert_eq!(
+        Reflect::get(&attributes, &"label".into()).ok(),
+        Some(JsValue::from("plop"))
+    );
+    let properties = Reflect::get(&instance, &"properties".into()).expect("properties");
+    assert_eq!(
+        Reflect::get(&properties, &"step".into()).ok(),
+        Some(JsValue::from(3.0))
+    );
+    let messages = Reflect::get(&instance, &"messages".into()).expect("messages");
+    assert!(Array::from(&messages).length() > 0);
+    let renders = Reflect::get(&instance, &"renders".into()).expect("renders");
+    assert!(renders.as_f64().unwrap_or_default() > 0.0);
+}
+
+#[wasm_bindgen_test]
+async fn should_batch_property_updates() {
+    register_point();
+    let point = mount("<plop-test-point></plop-test-point>");
+    await_render().await;
+    RENDERED_POINTS.with_borrow_mut(Vec::clear);
+
+    point.set_property("x", 1.0);
+    point.set_property("y", 2.0);
+    await_render().await;
+    assert_eq!(point.text_content(), "1,2");
+    assert_eq!(RENDERED_POINTS.take(), vec![(1.0, 2.0)]);
+}
+
+#[wasm_bindgen_test]
+async fn should_keep_property_set_before_upgrade() {
+    let title = mount("<plop-test-title></plop-test-title>");
+    title.set_property("title", "before upgrade");
+    register_title();
+    await_render().await;
+    assert_eq!(title.text_content(), "before upgrade");
+}
+
+#[wasm_bindgen_test]
+async fn should_keep_property_set_before_connect() {
+    register_counter();
+    let document = window()
+        .and_then(|window| window.document())
+        .expect("a document");
+    let counter = document
+        .create_element("plop-test-counter")
+        .expect("a counter");
+    Reflect::set(&counter, &"step".into(), &3.0.into()).expect("set the step");
+    await_render().await;
+
+    let body = document.body().expect("a body");
+    body.append_child(&counter).expect("connect the counter");
+    await_render().await;
+    let step = Reflect::get(&counter, &"step".into()).expect("a step promise");
+    let step = JsFuture::from(step.unchecked_into::<Promise>())
+        .await
+        .expect("the step");
+    assert_eq!(step, JsValue::from(3.0));
+    counter.remove();
+}
+
+#[wasm_bindgen_test]
+async fn should_resolve_update_complete() {
+    register_lifecycle();
+    let lifecycle = mount("<plop-test-lifecycle></plop-test-lifecycle>");
+    let rendered = lifecycle.collect_events("dx-rendered");
+    lifecycle.set_property("title", "plop");
+    let update_complete =
+        Reflect::get(lifecycle.element(), &"updateComplete".into()).expect("a promise");
+    let update_complete = JsFuture::from(update_complete.unchecked_into::<Promise>())
+        .await
+        .expect("the update is complete");
+    assert_eq!(update_complete, JsValue::TRUE);
+    assert_eq!(lifecycle.text_content(), "plop");
+    await_render().await;
+    assert!(!rendered.is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn should_resolve_update_complete_after_disconnect() {
+    register_lifecycle();
+    let lifecycle = mount("<plop-test-lifecycle></plop-test-lifecycle>");
+    await_render().await;
+    let element = lifecycle.element().clone();
+    drop(lifecycle);
+
+    Reflect::set(&element, &"title".into(), &"detached".into()).expect("set the title");
+    let update_complete = Reflect::get(&element, &"updateComplete".into()).expect("a promise");
+    let update_complete = JsFuture::from(update_complete.unchecked_into::<Promise>())
+        .await
+        .expect("the update is settled");
+    assert_eq!(update_complete, JsValue::FALSE);
+}
+
+#[wasm_bindgen_test]
+async fn should_dispatch_lifecycle_events() {
+    register_lifecycle();
+    let document = window()
+        .and_then(|window| window.document())
+        .expect("a document");
+    let lifecycle = document
+        .create_element("plop-test-lifecycle")
+        .expect("a lifecycle element");
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut listeners = vec![];
+    for event_type in ["dx-connected", "dx-rendered", "dx-disconnected"] {
+        let events = Rc::clone(&events);
+        let listener = Closure::<dyn Fn(Event)>::new(move |event: Event| {
+            events.borrow_mut().push(event.type_());
+        });
+        lifecycle
+            .add_event_listener_with_callback(event_type, listener.as_ref().unchecked_ref())
+            .expect("a listener");
+        listeners.push(listener);
+    }
+
+    let body = document.body().expect("a body");
+    body.append_child(&lifecycle).expect("connect");
+    await_render().await;
+    lifecycle.remove();
+    assert_eq!(
+        events.take(),
+        vec!["dx-connected", "dx-rendered", "dx-disconnected"]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_render_shared_runtime_instances_in_their_host() {
+    register_cell();
+    let cells = mount(
+        "<div>
+            <plop-test-cell label='a'></plop-test-cell>
+            <plop-test-cell label='b'></plop-test-cell>
+            <plop-test-cell label='c'></plop-test-cell>
+        </div>",
+    );
+    let container = cells.element();
+    let cell = |label: &str| {
+        container
+            .query_selector(&format!("plop-test-cell[label='{label}']"))
+            .expect("a valid selector")
+            .expect("a cell")
+    };
+    let rendered = || {
+        let hosts = container
+            .query_selector_all("plop-test-cell")
+            .expect("a valid selector");
+        (0..hosts.length())
+            .filter_map(|index| hosts.item(index))
+            .map(|host| host.text_content().unwrap_or_default())
+            .collect::<Vec<_>>()
+    };
+    await_render().await;
+    assert_eq!(rendered(), vec!["a", "b", "c"]);
+
+    // Disconnect
+    cell("b").remove();
+    await_render().await;
+    assert_eq!(rendered(), vec!["a", "c"]);
+
+    // Reorder
+    let (cell_a, cell_c) = (cell("a"), cell("c"));
+    container
+        .insert_before(&cell_c, Some(&cell_a))
+        .expect("move c");
+    await_render().await;
+    assert_eq!(rendered(), vec!["c", "a"]);
+
+    // Connect, and update after the list edits
+    let document = window()
+        .and_then(|window| window.document())
+        .expect("a document");
+    let cell_d = document.create_element("plop-test-cell").expect("a cell");
+    cell_d.set_attribute("label", "d").expect("a label");
+    container
+        .insert_before(&cell_d, Some(&cell_a))
+        .expect("insert d");
+    await_render().await;
+    cell_a.set_attribute("label", "A").expect("a label");
+    cell_c.remove();
+    await_render().await;
+    assert_eq!(rendered(), vec!["d", "A"]);
+}
+
+#[wasm_bindgen_test]
+async fn should_provide_dom_context_to_connected_consumer() {
+    register_theme_provider();
+    register_theme_consumer();
+    let provider = mount(
+        "<plop-test-theme-provider theme='dark'>
+            <plop-test-theme-consumer></plop-test-theme-consumer>
+        </plop-test-theme-provider>",
+    );
+    await_render().await;
+    assert_eq!(consumer_theme(&provider), "dark");
+
+    provider.set_attribute("theme", "light");
+    await_render().await;
+    await_render().await;
+    assert_eq!(consumer_theme(&provider), "light");
+}
+
+#[wasm_bindgen_test]
+async fn should_provide_dom_context_to_consumer_connected_before_provider() {
+    register_theme_consumer();
+    let provider = mount(
+        "<plop-test-late-theme-provider theme='dark'>
+            <plop-test-theme-consumer></plop-test-theme-consumer>
+        </plop-test-late-theme-provider>",
+    );
+    await_render().await;
+    assert_eq!(consumer_theme(&provider), "");
+
+    // The late provider announces itself, the consumer requests the context again
+    register_late_theme_provider();
+    await_render().await;
+    await_render().await;
+    assert_eq!(consumer_theme(&provider), "dark");
+
+    provider.set_attribute("theme", "light");
+    await_render().await;
+    await_render().await;
+    assert_eq!(consumer_theme(&provider), "light");
+}
+
+/// The theme rendered by the consumer of the provider
+fn consumer_theme(provider: &Mounted) -> String {
+    provider
+        .element()
+        .query_selector("plop-test-theme-consumer")
+        .expect("a valid selector")
+        .and_then(|consumer| consumer.shadow_root())
+        .and_then(|root| root.text_content())
+        .unwrap_or_default()
+}
+
+#[wasm_bindgen_test]
+async fn should_set_aria_with_element_internals() {
+    capture_element_internals();
+    register_aria_switch();
+    let switch = mount("<plop-test-aria></plop-test-aria>");
+    await_render().await;
+    let internals = Reflect::get(switch.element(), &"testInternals".into()).expect("internals");
+    let aria = |name: &str| Reflect::get(&internals, &name.into()).expect("an ARIA property");
+    assert_eq!(aria("role"), JsValue::from("switch"));
+    assert_eq!(aria("ariaChecked"), JsValue::from("false"));
+    assert_eq!(aria("ariaLabel"), JsValue::NULL);
+    // Not reflected on the host
+    assert!(!switch.element().has_attribute("aria-checked"));
+
+    switch.set_attribute("checked", "true");
+    await_render().await;
+    assert_eq!(aria("ariaChecked"), JsValue::from("true"));
+    assert_eq!(aria("ariaLabel"), JsValue::from("On"));
+
+    // The stale values are removed
+    switch.set_attribute("checked", "false");
+    await_render().await;
+    assert_eq!(aria("ariaChecked"), JsValue::from("false"));
+    assert_eq!(aria("ariaLabel"), JsValue::NULL);
+}
+
+#[wasm_bindgen_test]
+async fn should_fallback_to_aria_attribute() {
+    register_aria_switch();
+    let switch = mount("<plop-test-aria></plop-test-aria>");
+    await_render().await;
+    assert_eq!(switch.element().get_attribute("aria-plop"), None);
+
+    switch.set_attribute("checked", "true");
+    await_render().await;
+    assert_eq!(
+        switch.element().get_attribute("aria-plop").as_deref(),
+        Some("on")
+    );
+
+    // The stale attribute is removed
+    switch.set_attribute("checked", "false");
+    await_render().await;
+    assert_eq!(switch.element().get_attribute("aria-plop"), None);
+}
+
+/// Keep the `ElementInternals` of the next custom elements in their `testInternals` property
+fn capture_element_internals() {
+    let patch = Function::new_no_args(
+        "const prototype = HTMLElement.prototype;
+        if (prototype.attachInternals.captured) {
+            return;
+        }
+        const attachInternals = prototype.attachInternals;
+        prototype.attachInternals = function () {
+            const internals = attachInternals.call(this);
+            this.testInternals = internals;
+            return internals;
+        };
+        prototype.attachInternals.captured = true;",
+    );
+    patch.call0(&JsValue::NULL).expect("patch attachInternals");
+}
+
+#[wasm_bindgen_test]
+async fn should_toggle_custom_state() {
+    register_state_toggle();
+    let toggle = mount("<plop-test-state></plop-test-state>");
+    await_render().await;
+    assert!(!has_state(toggle.element(), "checked"));
+
+    toggle.set_attribute("checked", "true");
+    await_render().await;
+    assert!(has_state(toggle.element(), "checked"));
+
+    toggle.set_attribute("checked", "false");
+    await_render().await;
+    assert!(!has_state(toggle.element(), "checked"));
+}
+
+/// Match the `:state(name)` pseudo-class, or the legacy `:--name` form in older browsers
+fn has_state(element: &HtmlElement, name: &str) -> bool {
+    element
+        .matches(&format!(":state({name})"))
+        .or_else(|_| element.matches(&format!(":--{name}")))
+        .expect("a supported custom state selector")
+}
@@ -0,0 +1,184 @@
+use dioxus::logger::tracing::debug;
+use dioxus::prelude::{use_context, use_effect, use_hook, Writable as _};
+use dioxus::signals::{CopyValue, Readable as _};
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+use web_sys::HtmlElement;
+
+use crate::Shared;
+
+#[wasm_bindgen]
+extern "C" {
+    /// The `ElementInternals` of the web component
+    ///
+    /// See [MDN - ElementInternals](https://developer.mozilla.org/en-US/docs/Web/API/ElementInternals)
+    #[wasm_bindgen(extends = js_sys::Object, typescript_type = "ElementInternals")]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub type ElementInternals;
+}
+
+/// The ARIA role and states of the web component host
+///
+/// The names are the `ElementInternals` properties, e.g. `role`, or `ariaPressed`.
+/// Without `ElementInternals`, the host attributes are used instead, e.g. `role`, or `aria-pressed`.
+///
+/// See [MDN - ARIA](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aria {
+    values: Vec<(String, String)>,
+}
+
+impl Aria {
+    /// Create an empty ARIA definition
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a value, e.g. `aria.set("ariaValueNow", "42")`
+    #[must_use]
+    pub fn set(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let value = value.into();
+        if let Some(entry) = self.values.iter_mut().find(|(it, _)| *it == name) {
+            entry.1 = value;
+        } else {
+            self.values.push((name, value));
+        }
+        self
+    }
+
+    /// Set the `role`
+    #[must_use]
+    pub fn role(self, role: impl Into<String>) -> Self {
+        self.set("role", role)
+    }
+
+    /// Set the `aria-label`
+    #[must_use]
+    pub fn label(self, label: impl Into<String>) -> Self {
+        self.set("ariaLabel", label)
+    }
+
+    /// Set the `aria-pressed` state
+    #[must_use]
+    pub fn pressed(self, pressed: bool) -> Self {
+        self.set("ariaPressed", pressed.to_string())
+    }
+
+    /// Set the `aria-checked` state
+    #[must_use]
+    pub fn checked(self, checked: bool) -> Self {
+        self.set("ariaChecked", checked.to_string())
+    }
+
+    /// Set the `aria-expanded` state
+    #[must_use]
+    pub fn expanded(self, expanded: bool) -> Self {
+        self.set("ariaExpanded", expanded.to_string())
+    }
+
+    /// Set the `aria-selected` state
+    #[must_use]
+    pub fn selected(self, selected: bool) -> Self {
+        self.set("ariaSelected", selected.to_string())
+    }
+
+    /// Set the `aria-disabled` state
+    #[must_use]
+    pub fn disabled(self, disabled: bool) -> Self {
+        self.set("ariaDisabled", disabled.to_string())
+    }
+
+    /// Set the `aria-hidden` state
+    #[must_use]
+    pub fn hidden(self, hidden: bool) -> Self {
+        self.set("ariaHidden", hidden.to_string())
+    }
+}
+
+/// Write the ARIA role and states of the web component host
+///
+/// The closure is reactive: the values are updated when the signals read by the closure change,
+/// and a value missing from the new definition is removed.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{use_aria, web_component, Aria};
+///
+/// #[web_component(tag = "plop-toggle", role = "button")]
+/// fn Toggle() -> Element {
+///     let mut pressed = use_signal(|| false);
+///     use_aria(move || Aria::new().pressed(pressed()));
+///     rsx! {
+///         span { onclick: move |_| pressed.toggle(), "Toggle" }
+///     }
+/// }
+/// ```
+pub fn use_aria(mut aria: impl FnMut() -> Aria + 'static) {
+    let shared = use_context::<Shared>();
+    let mut previous = use_hook(|| CopyValue::new(Aria::default()));
+
+    use_effect(move || {
+        let aria = aria();
+        let internals = shared.internals();
+        let set = |name: &str, value: Option<&str>| match shared.try_event_target() {
+            Some(host) => set_aria(host, internals, name, value),
+            // The native test mode, only the host attributes
+            None => shared.reflect_attribute(&aria_attribute(name), value),
+        };
+        for (name, _) in &previous.read().values {
+            if !aria.values.iter().any(|(it, _)| it == name) {
+                set(name, None);
+            }
+        }
+        for (name, value) in &aria.values {
+            set(name, Some(value));
+        }
+        previous.set(aria);
+    });
+}
+
+/// Set, or remove, an ARIA value of the host, with a fallback to the host attribute
+pub(crate) fn set_aria(
+    host: &HtmlElement,
+    internals: Option<&ElementInternals>,
+    name: &str,
+    value: Option<&str>,
+) {
+    debug!(%name, ?value, "set ARIA");
+    // The ARIA reflection of `ElementInternals` is not available in all browsers
+    if let Some(internals) = internals.filter(|it| Reflect::has(it, &name.into()).unwrap_or(false))
+    {
+        let value = value.map_or(JsValue::NULL, JsValue::from);
+        if Reflect::set(internals, &name.into(), &value).is_ok() {
+            return;
+        }
+    }
+
+    let attribute = aria_attribute(name);
+    let _ = match value {
+        Some(value) => host.set_attribute(&attribute, value),
+        None => host.remove_attribute(&attribute),
+    };
+}
+
+/// Set the default role of the host, an existing `role` attribute is kept
+pub(crate) fn set_default_role(
+    host: &HtmlElement,
+    internals: Option<&ElementInternals>,
+    role: &str,
+) {
+    if host.has_attribute("role") {
+        return;
+    }
+    set_aria(host, internals, "role", Some(role));
+}
+
+/// The host attribute of an `ElementInternals` ARIA property, e.g. `ariaValueNow` -> `aria-valuenow`
+fn aria_attribute(name: &str) -> String {
+    name.strip_prefix("aria").map_or_else(
+        || name.to_string(),
+        |suffix| format!("aria-{}", suffix.to_lowercase()),
+    )
+}
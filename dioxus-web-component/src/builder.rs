@@ -0,0 +1,433 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use dioxus::dioxus_core::{DynamicNode, Element, VComponent};
+use dioxus::logger::tracing::{warn, Level};
+use dioxus::prelude::{
+    consume_context, use_context, use_context_provider, use_coroutine, use_effect, use_hook,
+    EventHandler, ReadOnlySignal, Readable as _, Signal, Writable as _,
+};
+use futures::StreamExt as _;
+use wasm_bindgen::JsValue;
+use web_sys::CustomElementRegistry;
+
+use crate::registry::{self, RegisteredComponent, RegistrationError};
+use crate::rust_component::RustComponent;
+use crate::{
+    register_web_component, CustomEventOptions, DioxusWebComponent, InjectedStyle, Property, Shared,
+};
+
+/// Build and register a web component at runtime, without the `#[web_component]` macro
+///
+/// The component reads the values with [`use_attribute`], [`use_property`],
+/// and dispatches custom events with [`use_custom_event`].
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{
+///     use_attribute, use_custom_event, CustomEventOptions, WebComponentBuilder,
+/// };
+///
+/// #[allow(non_snake_case)]
+/// fn Greetings() -> Element {
+///     let name = use_attribute("name");
+///     let on_greet = use_custom_event("greet");
+///     rsx! {
+///         button {
+///             onclick: move |_| on_greet(name().into()),
+///             "Hello {name().unwrap_or_default()}!"
+///         }
+///     }
+/// }
+///
+/// WebComponentBuilder::new("plop-greeting")
+///     .attribute("name")
+///     .event("greet", CustomEventOptions::default())
+///     .register(Greetings);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct WebComponentBuilder {
+    tag: String,
+    attributes: Vec<String>,
+    properties: Vec<Property>,
+    events: Vec<(String, CustomEventOptions)>,
+    style: InjectedStyle,
+    shared_runtime: bool,
+    role: Option<String>,
+    states: Vec<String>,
+    parts: Vec<String>,
+    css_properties: Vec<String>,
+    log_level: Option<Level>,
+    lifecycle_events: bool,
+    custom_element_registry: Option<CustomElementRegistry>,
+}
+
+impl WebComponentBuilder {
+    /// Create a builder for the custom element tag
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            attributes: vec![],
+            properties: vec![],
+            events: vec![],
+            style: InjectedStyle::default(),
+            shared_runtime: false,
+            role: None,
+            states: vec![],
+            parts: vec![],
+            css_properties: vec![],
+            log_level: None,
+            lifecycle_events: false,
+            custom_element_registry: None,
+        }
+    }
+
+    /// Add an HTML attribute
+    pub fn attribute(mut self, name: impl Into<String>) -> Self {
+        self.attributes.push(name.into());
+        self
+    }
+
+    /// Add a Javascript property
+    pub fn property(mut self, name: impl Into<String>, readonly: bool) -> Self {
+        self.properties.push(Property::new(name, readonly));
+        self
+    }
+
+    /// Add a custom event
+    pub fn event(mut self, name: impl Into<String>, options: CustomEventOptions) -> Self {
+        self.events.push((name.into(), options));
+        self
+    }
+
+    /// Set the style
+    pub fn style(mut self, style: InjectedStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Render all the instances with a single Dioxus application
+    ///
+    /// It saves memory and startup time when a page contains many instances,
+    /// but the component is rendered in the light DOM of the host, without shadow DOM.
+    /// So `<slot>` are not available, and the style is injected once in the document `<head>`.
+    /// The host elements should not be inside a shadow root.
+    pub fn shared_runtime(mut self, shared_runtime: bool) -> Self {
+        self.shared_runtime = shared_runtime;
+        self
+    }
+
+    /// Set the default ARIA role of the host, e.g. `button`
+    ///
+    /// The role is set with `ElementInternals`, or with the host `role` attribute if not supported.
+    /// A `role` attribute set by the page takes precedence.
+    pub fn role(mut self, role: impl Into<String>) -> Self {
+        self.role = Some(role.into());
+        self
+    }
+
+    /// Declare a custom state, see [`use_custom_state`](crate::use_custom_state)
+    pub fn state(mut self, name: impl Into<String>) -> Self {
+        self.states.push(name.into());
+        self
+    }
+
+    /// Declare a CSS part, to style the component from the outside with `::part(...)`
+    ///
+    /// In debug builds, a warning is logged when the part is not in the rendered template.
+    pub fn part(mut self, name: impl Into<String>) -> Self {
+        self.parts.push(name.into());
+        self
+    }
+
+    /// Declare a CSS custom property, e.g. `--plop-color`, used by the component style
+    pub fn css_property(mut self, name: impl Into<String>) -> Self {
+        self.css_properties.push(name.into());
+        self
+    }
+
+    /// Set the most verbose level of the logs of the instances, e.g. `Level::WARN` to hide the debug logs
+    ///
+    /// Each instance logs in a `web_component` span, with the `tag`, the `instance` id,
+    /// and the `id` attribute of the host.
+    /// The level applies to the runtime logs of the instances, and to the logs of their Dioxus application
+    /// (except with the shared runtime, where the application is shared by all instances).
+    pub fn log_level(mut self, level: Level) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Dispatch the lifecycle events from the host
+    ///
+    /// - `dx-connected` when the host is connected,
+    /// - `dx-rendered` after the first render, and after the render of the attributes and properties changes,
+    /// - `dx-disconnected` when the host is disconnected.
+    ///
+    /// The events don't bubble.
+    /// The renders triggered by the internal state of the component are not notified.
+    pub fn lifecycle_events(mut self, lifecycle_events: bool) -> Self {
+        self.lifecycle_events = lifecycle_events;
+        self
+    }
+
+    /// Define the web component in a scoped custom element registry, instead of the global `customElements`
+    ///
+    /// The registry is also attached to the shadow root of the web component,
+    /// so the custom elements rendered by the component are resolved with this registry.
+    /// It allows different versions of the same web component to coexist in the page.
+    ///
+    /// See [Scoped Custom Element Registries](https://github.com/WICG/webcomponents/blob/gh-pages/proposals/Scoped-Custom-Element-Registries.md)
+    pub fn custom_element_registry(mut self, registry: CustomElementRegistry) -> Self {
+        self.custom_element_registry = Some(registry);
+        self
+    }
+
+    /// Register the web component
+    ///
+    /// The registration is skipped if the tag is invalid or already used, see [`Self::try_register`].
+    pub fn register(self, component: fn() -> Element) {
+        if let Err(error) = self.try_register(component) {
+            warn!(%error, "skip the registration");
+        }
+    }
+
+    /// Register the web component
+    ///
+    /// # Errors
+    ///
+    /// Fail if the tag is invalid, already registered, or already defined in the custom element registry
+    pub fn try_register(self, component: fn() -> Element) -> Result<(), RegistrationError> {
+        let definition = DynamicDefinition {
+            properties: self.properties.iter().map(Property::name).collect(),
+            events: self.events.iter().cloned().collect(),
+            component,
+        };
+        self.register_component(dynamic_builder, Some(Arc::new(definition)))
+    }
+
+    /// Register the web component with a builder function,
+    /// see [`register_dioxus_web_component`](crate::register_dioxus_web_component)
+    ///
+    /// This is used by the `#[web_component]` macro.
+    /// The registration is skipped if the tag is invalid or already used, see [`Self::try_register_with_builder`].
+    pub fn register_with_builder(self, dx_el_builder: fn() -> Element) {
+        if let Err(error) = self.try_register_with_builder(dx_el_builder) {
+            warn!(%error, "skip the registration");
+        }
+    }
+
+    /// Register the web component with a builder function
+    ///
+    /// # Errors
+    ///
+    /// Fail if the tag is invalid, already registered, or already defined in the custom element registry
+    pub fn try_register_with_builder(
+        self,
+        dx_el_builder: fn() -> Element,
+    ) -> Result<(), RegistrationError> {
+        self.register_component(dx_el_builder, None)
+    }
+
+    fn register_component(
+        self,
+        dx_el_builder: fn() -> Element,
+        definition: Option<Arc<DynamicDefinition>>,
+    ) -> Result<(), RegistrationError> {
+        let Self {
+            tag,
+            attributes,
+            properties,
+            events,
+            style,
+            shared_runtime,
+            role,
+            states,
+            parts,
+            css_properties,
+            log_level,
+            lifecycle_events,
+            custom_element_registry,
+        } = self;
+        let tag = registry::prefixed_tag(&tag);
+        registry::check_available(&tag, custom_element_registry.as_ref())?;
+
+        let rust_component = RustComponent {
+            attributes: attributes.clone(),
+            properties: properties.clone(),
+            style: style.clone(),
+            dx_el_builder,
+            definition,
+            shared_runtime,
+            tag: tag.clone(),
+            role: role.clone(),
+            parts: parts.clone(),
+            log_level,
+            lifecycle_events,
+            style_injected: Cell::default(),
+        };
+        register_web_component(&tag, rust_component, custom_element_registry.as_ref());
+
+        registry::add(RegisteredComponent {
+            tag,
+            attributes,
+            properties,
+            events,
+            style,
+            shared_runtime,
+            role,
+            states,
+            parts,
+            css_properties,
+            lifecycle_events,
+            custom_element_registry,
+        });
+        Ok(())
+    }
+}
+
+/// The part of the web component only known at runtime
+#[derive(Debug)]
+pub(crate) struct DynamicDefinition {
+    properties: Vec<String>,
+    events: HashMap<String, CustomEventOptions>,
+    component: fn() -> Element,
+}
+
+/// The values of a web component registered with the [`WebComponentBuilder`]
+#[derive(Clone)]
+struct DynamicWebComponent {
+    shared: Shared,
+    attributes: Rc<HashMap<String, Signal<Option<String>>>>,
+    properties: Rc<HashMap<String, Signal<JsValue>>>,
+    definition: Arc<DynamicDefinition>,
+}
+
+impl DioxusWebComponent for DynamicWebComponent {
+    fn set_attribute(&mut self, attribute: &str, value: Option<String>) {
+        if let Some(mut signal) = self.attributes.get(attribute).copied() {
+            signal.set(value);
+        } else {
+            warn!("No attribute {attribute} to set");
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: JsValue) {
+        if let Some(mut signal) = self.properties.get(property).copied() {
+            signal.set(value);
+        } else {
+            warn!("No property {property} to set");
+        }
+    }
+
+    fn get_property(&mut self, property: &str) -> JsValue {
+        self.properties.get(property).map_or_else(
+            || {
+                warn!("No property {property} to get");
+                JsValue::undefined()
+            },
+            |signal| signal.peek().clone(),
+        )
+    }
+}
+
+fn dynamic_builder() -> Element {
+    let mut shared = use_context::<Shared>();
+    let definition = use_context::<Arc<DynamicDefinition>>();
+
+    let web_component = use_hook(|| {
+        let attributes = shared
+            .attributes
+            .iter()
+            .map(|name| (name.clone(), Signal::new(None)))
+            .collect();
+        let properties = definition
+            .properties
+            .iter()
+            .map(|name| (name.clone(), Signal::new(JsValue::undefined())))
+            .collect();
+        DynamicWebComponent {
+            shared: shared.clone(),
+            attributes: Rc::new(attributes),
+            properties: Rc::new(properties),
+            definition: Arc::clone(&definition),
+        }
+    });
+    use_context_provider(|| web_component.clone());
+
+    let coroutine = use_coroutine(move |mut rx| {
+        let mut web_component = web_component.clone();
+        async move {
+            while let Some(message) = rx.next().await {
+                web_component.handle_message(message);
+            }
+        }
+    });
+
+    use_effect(move || {
+        shared.set_tx(coroutine.tx());
+    });
+
+    let component = VComponent::new(definition.component, (), "DynamicWebComponent");
+    render_node(DynamicNode::Component(component))
+}
+
+fn render_node(node: DynamicNode) -> Element {
+    #[allow(clippy::wildcard_imports)]
+    use dioxus::prelude::*;
+
+    rsx! {
+        {node}
+    }
+}
+
+/// Read an HTML attribute of a web component registered with the [`WebComponentBuilder`]
+///
+/// The value is `None` if the attribute is missing.
+#[must_use]
+pub fn use_attribute(name: &str) -> ReadOnlySignal<Option<String>> {
+    use_hook(|| {
+        let web_component = consume_context::<DynamicWebComponent>();
+        let signal = web_component.attributes.get(name).copied();
+        signal
+            .unwrap_or_else(|| {
+                warn!("No attribute {name} registered");
+                Signal::new(None)
+            })
+            .into()
+    })
+}
+
+/// Read or write a Javascript property of a web component registered with the [`WebComponentBuilder`]
+#[must_use]
+pub fn use_property(name: &str) -> Signal<JsValue> {
+    use_hook(|| {
+        let web_component = consume_context::<DynamicWebComponent>();
+        let signal = web_component.properties.get(name).copied();
+        signal.unwrap_or_else(|| {
+            warn!("No property {name} registered");
+            Signal::new(JsValue::undefined())
+        })
+    })
+}
+
+/// Create an handler that dispatch a custom event
+/// of a web component registered with the [`WebComponentBuilder`]
+#[must_use]
+pub fn use_custom_event(name: &str) -> EventHandler<JsValue> {
+    use_hook(|| {
+        let web_component = consume_context::<DynamicWebComponent>();
+        let options = web_component
+            .definition
+            .events
+            .get(name)
+            .copied()
+            .unwrap_or_else(|| {
+                warn!("No event {name} registered");
+                CustomEventOptions::default()
+            });
+        web_component.shared.event_handler(name, options)
+    })
+}
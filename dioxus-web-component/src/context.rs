@@ -0,0 +1,63 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+
+use dioxus::dioxus_core::VirtualDom;
+
+type ContextProvider = Box<dyn Fn() -> Box<dyn Any>>;
+
+thread_local! {
+    static GLOBAL_CONTEXTS: RefCell<Vec<(TypeId, ContextProvider)>> = RefCell::default();
+}
+
+/// Provide a context to all the web components
+///
+/// The components read the value with `use_context::<T>()`, as in a normal Dioxus application.
+/// It's useful to share services, like an i18n catalog, an API client, or a theme.
+///
+/// Providing a value of the same type replaces the previous value.
+/// The value is injected when a web component instance is connected,
+/// so call this function before adding the web components to the page.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{provide_global_context, web_component};
+///
+/// #[derive(Clone)]
+/// struct Theme {
+///     color: String,
+/// }
+///
+/// #[web_component(tag = "plop-title")]
+/// fn Title(title: String) -> Element {
+///     let theme = use_context::<Theme>();
+///     rsx! { h1 { color: theme.color, "{title}" } }
+/// }
+///
+/// provide_global_context(Theme {
+///     color: "rebeccapurple".to_string(),
+/// });
+/// register_title();
+/// ```
+pub fn provide_global_context<T>(value: T)
+where
+    T: Clone + 'static,
+{
+    let type_id = TypeId::of::<T>();
+    let provider: ContextProvider = Box::new(move || Box::new(value.clone()));
+    GLOBAL_CONTEXTS.with_borrow_mut(|contexts| {
+        if let Some(entry) = contexts.iter_mut().find(|(id, _)| *id == type_id) {
+            entry.1 = provider;
+        } else {
+            contexts.push((type_id, provider));
+        }
+    });
+}
+
+/// Inject the global contexts into the Dioxus application
+pub(crate) fn provide_global_contexts(vdom: &mut VirtualDom) {
+    GLOBAL_CONTEXTS.with_borrow(|contexts| {
+        for (_, provider) in contexts {
+            vdom.insert_any_root_context(provider());
+        }
+    });
+}
@@ -0,0 +1,70 @@
+use dioxus::logger::tracing::{debug, warn};
+use dioxus::prelude::{use_context, use_effect, ReadOnlySignal};
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+use crate::{ElementInternals, Shared};
+
+#[wasm_bindgen]
+extern "C" {
+    /// See [MDN - CustomStateSet](https://developer.mozilla.org/en-US/docs/Web/API/CustomStateSet)
+    type CustomStateSet;
+
+    #[wasm_bindgen(method, catch)]
+    fn add(this: &CustomStateSet, state: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn delete(this: &CustomStateSet, state: &str) -> bool;
+}
+
+/// Toggle a custom state of the web component host
+///
+/// The state can be used to style the web component from the outside,
+/// e.g. `plop-toggle:state(checked) { ... }`.
+/// Declare the states with the `states` of the `#[web_component]` macro,
+/// so they land in the generated TypeScript definition.
+///
+/// The state is ignored if the browser does not support `ElementInternals.states`.
+///
+/// See [MDN - `:state()`](https://developer.mozilla.org/en-US/docs/Web/CSS/:state)
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{use_custom_state, web_component};
+///
+/// #[web_component(tag = "plop-toggle", states = ["checked"])]
+/// fn Toggle() -> Element {
+///     let mut checked = use_signal(|| false);
+///     use_custom_state("checked", checked);
+///     rsx! {
+///         span { onclick: move |_| checked.toggle(), "Toggle" }
+///     }
+/// }
+/// ```
+pub fn use_custom_state(name: &str, active: impl Into<ReadOnlySignal<bool>>) {
+    let shared = use_context::<Shared>();
+    let active = active.into();
+    let name = name.to_string();
+
+    use_effect(move || {
+        let active = active();
+        let Some(states) = shared.internals().and_then(custom_states) else {
+            warn!(%name, "Custom states are not supported");
+            return;
+        };
+        debug!(%name, active, "set custom state");
+        if !active {
+            // Also remove the legacy `--name` form
+            states.delete(&name);
+            states.delete(&format!("--{name}"));
+        } else if states.add(&name).is_err() {
+            // Older browsers only accept the legacy `--name` form
+            let _ = states.add(&format!("--{name}"));
+        }
+    });
+}
+
+fn custom_states(internals: &ElementInternals) -> Option<CustomStateSet> {
+    let states = Reflect::get(internals, &"states".into()).ok()?;
+    (!states.is_undefined()).then(|| states.unchecked_into())
+}
@@ -0,0 +1,74 @@
+use dioxus::logger::tracing::debug;
+use wasm_bindgen::prelude::*;
+
+use crate::registry::prefixed_tag;
+
+/// Define web components only when one of their tags appears in the document
+///
+/// Until then, the elements are undefined custom elements (you can style them with `:not(:defined)`),
+/// they are upgraded when the registration function is called.
+///
+/// Only the `customElements.define` call, and the Dioxus applications of the instances, are deferred:
+/// the WASM module with all the web components is already loaded and initialized.
+///
+/// ```rust, no_run
+/// use dioxus_web_component::DeferredDefinition;
+///
+/// fn register_counter() {
+///     // generated by the `#[web_component]` macro
+/// }
+///
+/// DeferredDefinition::new(["plop-counter"])
+///     .upgrade_when_visible(true)
+///     .register(register_counter);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct DeferredDefinition {
+    tags: Vec<String>,
+    upgrade_when_visible: bool,
+}
+
+impl DeferredDefinition {
+    /// Create a deferred definition for a list of tags
+    pub fn new<I>(tags: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            tags: tags.into_iter().map(Into::into).collect(),
+            upgrade_when_visible: false,
+        }
+    }
+
+    /// Wait until one of the elements is visible in the viewport
+    ///
+    /// See [MDN - Intersection Observer API](https://developer.mozilla.org/en-US/docs/Web/API/Intersection_Observer_API)
+    pub fn upgrade_when_visible(mut self, upgrade_when_visible: bool) -> Self {
+        self.upgrade_when_visible = upgrade_when_visible;
+        self
+    }
+
+    /// Call the `register` function when the first element appears
+    ///
+    /// The function is not called if all the tags are already defined elsewhere.
+    /// The tag prefix is applied, see [`set_tag_prefix`](crate::set_tag_prefix).
+    /// Only the document tree is observed, not the shadow roots.
+    pub fn register(self, register: impl FnOnce() + 'static) {
+        let Self {
+            tags,
+            upgrade_when_visible,
+        } = self;
+        let tags = tags.iter().map(|tag| prefixed_tag(tag)).collect::<Vec<_>>();
+        debug!(?tags, upgrade_when_visible, "deferred definition");
+        let register = Closure::once_into_js(register);
+        register_deferred(tags, upgrade_when_visible, &register);
+    }
+}
+
+#[wasm_bindgen(module = "/src/shim.js")]
+extern "C" {
+    #[allow(unsafe_code)]
+    fn register_deferred(tags: Vec<String>, upgrade_when_visible: bool, register: &JsValue);
+}
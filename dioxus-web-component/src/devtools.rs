@@ -0,0 +1,294 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+
+use dioxus::prelude::{queue_effect, try_consume_context};
+use futures::channel::oneshot;
+use js_sys::{Array, Object, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+use web_sys::{window, HtmlElement};
+
+use crate::{Message, MessageChannel, Shared, SharedJsValue};
+
+/// The number of messages, and events, kept for each instance
+const HISTORY_SIZE: usize = 20;
+
+/// The name of the devtools API on `window`
+const DEVTOOLS_API: &str = "__DIOXUS_WC__";
+
+thread_local! {
+    static DEVTOOLS: RefCell<Vec<DevtoolsInstance>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A live instance, as seen by the devtools
+pub(crate) struct DevtoolsInstance {
+    id: usize,
+    tag: String,
+    host: HtmlElement,
+    attributes: Vec<String>,
+    properties: Vec<String>,
+    tx: Arc<RwLock<MessageChannel>>,
+    messages: VecDeque<String>,
+    events: VecDeque<(String, JsValue)>,
+    /// The number of renders of the attributes and properties changes
+    renders: u32,
+    /// A render is queued after a change
+    render_queued: bool,
+}
+
+impl DevtoolsInstance {
+    pub(crate) fn new(
+        id: usize,
+        tag: String,
+        host: HtmlElement,
+        attributes: Vec<String>,
+        properties: Vec<String>,
+        tx: Arc<RwLock<MessageChannel>>,
+    ) -> Self {
+        Self {
+            id,
+            tag,
+            host,
+            attributes,
+            properties,
+            tx,
+            messages: VecDeque::new(),
+            events: VecDeque::new(),
+            renders: 0,
+            render_queued: false,
+        }
+    }
+
+    /// Capture the instance state, the property values are requested to the instance
+    fn snapshot(&self) -> Snapshot {
+        let properties = self
+            .properties
+            .iter()
+            .map(|name| (name.clone(), request_property(&self.tx, name)))
+            .collect();
+        Snapshot {
+            id: self.id,
+            tag: self.tag.clone(),
+            host: self.host.clone(),
+            attributes: self.attributes.clone(),
+            properties,
+            messages: self.messages.iter().cloned().collect(),
+            events: self.events.iter().cloned().collect(),
+            renders: self.renders,
+        }
+    }
+}
+
+/// The state of an instance, waiting for the property values
+struct Snapshot {
+    id: usize,
+    tag: String,
+    host: HtmlElement,
+    attributes: Vec<String>,
+    properties: Vec<(String, Option<oneshot::Receiver<SharedJsValue>>)>,
+    messages: Vec<String>,
+    events: Vec<(String, JsValue)>,
+    renders: u32,
+}
+
+impl Snapshot {
+    async fn into_object(self) -> Object {
+        let Self {
+            id,
+            tag,
+            host,
+            attributes,
+            properties,
+            messages,
+            events,
+            renders,
+        } = self;
+        let result = Object::new();
+        set(&result, "tag", &tag.into());
+        set(&result, "instance", &id.into());
+        set(&result, "host", &host);
+        set(&result, "renders", &renders.into());
+
+        let attribute_values = Object::new();
+        for attribute in &attributes {
+            set(
+                &attribute_values,
+                attribute,
+                &host.get_attribute(attribute).into(),
+            );
+        }
+        set(&result, "attributes", &attribute_values);
+
+        let property_values = Object::new();
+        for (property, rx) in properties {
+            let value = match rx {
+                Some(rx) => rx.await.map_or(JsValue::undefined(), |value| value.0),
+                None => JsValue::undefined(),
+            };
+            set(&property_values, &property, &value);
+        }
+        set(&result, "properties", &property_values);
+
+        let messages = messages.into_iter().map(JsValue::from).collect::<Array>();
+        set(&result, "messages", &messages);
+
+        let events = events
+            .into_iter()
+            .map(|(event_type, detail)| {
+                let event = Object::new();
+                set(&event, "type", &event_type.into());
+                set(&event, "detail", &detail);
+                event
+            })
+            .collect::<Array>();
+        set(&result, "events", &events);
+
+        result
+    }
+}
+
+fn set(target: &Object, key: &str, value: &JsValue) {
+    let _ = Reflect::set(target, &key.into(), value);
+}
+
+/// Track a connected instance
+pub(crate) fn connect(instance: DevtoolsInstance) {
+    install();
+    DEVTOOLS.with_borrow_mut(|instances| {
+        instances.retain(|it| it.id != instance.id);
+        instances.push(instance);
+    });
+}
+
+/// Forget a disconnected instance
+pub(crate) fn disconnect(id: usize) {
+    DEVTOOLS.with_borrow_mut(|instances| instances.retain(|it| it.id != id));
+}
+
+/// Keep the attribute or property change handled by the instance, and count its render
+///
+/// Called in the Dioxus runtime of the instance, the `Get` and `Flush` messages are skipped.
+/// The changes handled before the same render are counted once.
+pub(crate) fn record_message(message: &Message) {
+    if matches!(message, Message::Get { .. } | Message::Flush { .. }) {
+        return;
+    }
+    let Some(shared) = try_consume_context::<Shared>() else {
+        return;
+    };
+    let Some(host) = shared.try_event_target() else {
+        return;
+    };
+    DEVTOOLS.with_borrow_mut(|instances| {
+        let Some(instance) = instances.iter_mut().find(|it| it.host == *host) else {
+            return;
+        };
+        push_bounded(&mut instance.messages, format!("{message:?}"));
+        if !instance.render_queued {
+            instance.render_queued = true;
+            let host = host.clone();
+            queue_effect(move || record_render(&host));
+        }
+    });
+}
+
+/// Count the render of the recorded changes
+fn record_render(host: &HtmlElement) {
+    DEVTOOLS.with_borrow_mut(|instances| {
+        if let Some(instance) = instances.iter_mut().find(|it| it.host == *host) {
+            instance.render_queued = false;
+            instance.renders += 1;
+        }
+    });
+}
+
+/// Keep the custom event dispatched by an instance host
+pub(crate) fn record_event(target: &JsValue, event_type: &str, detail: &JsValue) {
+    DEVTOOLS.with_borrow_mut(|instances| {
+        let host = instances
+            .iter_mut()
+            .find(|it| AsRef::<JsValue>::as_ref(&it.host) == target);
+        if let Some(instance) = host {
+            push_bounded(
+                &mut instance.events,
+                (event_type.to_string(), detail.clone()),
+            );
+        }
+    });
+}
+
+fn push_bounded<T>(history: &mut VecDeque<T>, value: T) {
+    if history.len() == HISTORY_SIZE {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+/// Install the `window.__DIOXUS_WC__` API, once
+///
+/// - `instances()` returns a promise of the live instances, with their attributes and properties values,
+///   the recent messages, the recent dispatched events, and the renders count
+/// - `tags()` returns the number of live instances per tag
+fn install() {
+    let Some(window) = window() else {
+        return;
+    };
+    if Reflect::has(&window, &DEVTOOLS_API.into()).unwrap_or_default() {
+        return;
+    }
+
+    let api = Object::new();
+    let instances = Closure::<dyn Fn() -> Promise>::new(|| {
+        future_to_promise(async {
+            let snapshots = DEVTOOLS.with_borrow(|instances| {
+                instances
+                    .iter()
+                    .map(DevtoolsInstance::snapshot)
+                    .collect::<Vec<_>>()
+            });
+            let result = Array::new();
+            for snapshot in snapshots {
+                result.push(&snapshot.into_object().await.into());
+            }
+            Ok(result.into())
+        })
+    });
+    set(&api, "instances", instances.as_ref());
+    instances.forget();
+
+    let tags = Closure::<dyn Fn() -> Object>::new(|| {
+        let result = Object::new();
+        DEVTOOLS.with_borrow(|instances| {
+            for instance in instances {
+                let count = Reflect::get(&result, &instance.tag.as_str().into())
+                    .ok()
+                    .and_then(|value| value.as_f64())
+                    .unwrap_or_default();
+                set(&result, &instance.tag, &(count + 1.0).into());
+            }
+        });
+        result
+    });
+    set(&api, "tags", tags.as_ref());
+    tags.forget();
+
+    set(&window, DEVTOOLS_API, &api);
+}
+
+/// Ask the property value to the instance, like the `getProperty` of the host
+fn request_property(
+    tx: &Arc<RwLock<MessageChannel>>,
+    name: &str,
+) -> Option<oneshot::Receiver<SharedJsValue>> {
+    let channel = tx.read().ok()?;
+    let sender = channel.sender()?;
+    let (tx, rx) = oneshot::channel();
+    sender
+        .unbounded_send(Message::Get {
+            name: name.to_string(),
+            tx,
+        })
+        .ok()?;
+    Some(rx)
+}
@@ -0,0 +1,282 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use dioxus::logger::tracing::{debug, warn};
+use dioxus::prelude::{
+    use_context, use_coroutine, use_drop, use_effect, use_hook, use_signal, ReadOnlySignal,
+    Readable as _, Writable as _,
+};
+use futures::StreamExt as _;
+use js_sys::{Function, Reflect};
+use wasm_bindgen::prelude::*;
+use web_sys::{window, Document, Event, EventInit};
+
+use crate::Shared;
+
+/// The event name of the [Context Protocol]
+///
+/// [Context Protocol]: https://github.com/webcomponents-cg/community-protocols/blob/main/proposals/context.md
+const CONTEXT_REQUEST: &str = "context-request";
+
+/// The event dispatched by a provider when it connects, see the [Context Protocol]
+///
+/// [Context Protocol]: https://github.com/webcomponents-cg/community-protocols/blob/main/proposals/context.md#late-upgraded-context-providers
+const CONTEXT_PROVIDER: &str = "context-provider";
+
+/// Request a context from an ancestor element, with the [Context Protocol]
+///
+/// The provider could be another Dioxus web component using [`provide_dom_context`],
+/// or any web component implementing the protocol, e.g. a [Lit] `ContextProvider`.
+/// The key is the context identifier, a string in the case of a Lit context created with `createContext('key')`.
+///
+/// The value is `None` until a provider answers, or if the value cannot be converted.
+/// The value is updated when the provider changes it.
+/// The request is dispatched again when a provider of the key connects later,
+/// e.g. when the provider tag is registered after the consumer tag.
+///
+/// [Context Protocol]: https://github.com/webcomponents-cg/community-protocols/blob/main/proposals/context.md
+/// [Lit]: https://lit.dev/docs/data/context/
+#[must_use]
+pub fn use_dom_context<T>(key: &str) -> ReadOnlySignal<Option<T>>
+where
+    T: TryFrom<JsValue> + 'static,
+{
+    let shared = use_context::<Shared>();
+    let mut value = use_signal(|| None);
+
+    // The callback could be called outside the Dioxus runtime
+    let coroutine = use_coroutine(move |mut rx| async move {
+        while let Some(new_value) = rx.next().await {
+            value.set(T::try_from(new_value).ok());
+        }
+    });
+
+    let subscription = use_hook(|| {
+        let tx = coroutine.tx();
+        let unsubscribe = Rc::new(RefCell::new(None::<Function>));
+        let answered = Rc::new(Cell::new(false));
+        let callback = Closure::<dyn FnMut(JsValue, JsValue)>::new({
+            let unsubscribe = Rc::clone(&unsubscribe);
+            let answered = Rc::clone(&answered);
+            move |new_value: JsValue, unsubscribe_fn: JsValue| {
+                answered.set(true);
+                if let Ok(unsubscribe_fn) = unsubscribe_fn.dyn_into::<Function>() {
+                    unsubscribe.replace(Some(unsubscribe_fn));
+                }
+                let _ = tx.unbounded_send(new_value);
+            }
+        });
+
+        let callback = Rc::new(callback);
+
+        // No host element in the native test mode
+        let Some(host) = shared.try_event_target().cloned() else {
+            return Rc::new(Subscription {
+                _callback: callback,
+                unsubscribe,
+                late_provider: None,
+            });
+        };
+        let request = {
+            let key = key.to_string();
+            let callback = Rc::clone(&callback);
+            move || {
+                let callback = (*callback).as_ref().unchecked_ref();
+                let event = context_request_event(&key, &host, callback);
+                debug!(%key, "request context");
+                host.dispatch_event(&event).unwrap_throw();
+            }
+        };
+        request();
+
+        // Request again when a late provider connects, until a provider answers
+        let late_provider = (!answered.get()).then(|| {
+            let key = key.to_string();
+            Closure::<dyn Fn(Event)>::new(move |event: Event| {
+                if !answered.get()
+                    && get_property(&event, "context").as_string() == Some(key.clone())
+                {
+                    request();
+                }
+            })
+        });
+        if let (Some(listener), Some(document)) = (&late_provider, document()) {
+            document
+                .add_event_listener_with_callback(
+                    CONTEXT_PROVIDER,
+                    listener.as_ref().unchecked_ref(),
+                )
+                .unwrap_throw();
+        }
+
+        Rc::new(Subscription {
+            _callback: callback,
+            unsubscribe,
+            late_provider,
+        })
+    });
+
+    use_drop(move || {
+        if let Some(unsubscribe) = subscription.unsubscribe.take() {
+            let _ = unsubscribe.call0(&JsValue::NULL);
+        }
+        if let (Some(listener), Some(document)) = (&subscription.late_provider, document()) {
+            let _ = document.remove_event_listener_with_callback(
+                CONTEXT_PROVIDER,
+                listener.as_ref().unchecked_ref(),
+            );
+        }
+    });
+
+    value.into()
+}
+
+/// The state of a context consumer
+struct Subscription {
+    _callback: Rc<Closure<dyn FnMut(JsValue, JsValue)>>,
+    unsubscribe: Rc<RefCell<Option<Function>>>,
+    /// Listen to the late providers, `None` if answered by the first request
+    late_provider: Option<Closure<dyn Fn(Event)>>,
+}
+
+fn document() -> Option<Document> {
+    window().and_then(|window| window.document())
+}
+
+fn context_request_event(key: &str, target: &JsValue, callback: &Function) -> Event {
+    let init = EventInit::new();
+    init.set_bubbles(true);
+    init.set_composed(true);
+    let event = Event::new_with_event_init_dict(CONTEXT_REQUEST, &init).unwrap_throw();
+    set_property(&event, "context", &key.into());
+    set_property(&event, "contextTarget", target);
+    set_property(&event, "callback", callback);
+    set_property(&event, "subscribe", &true.into());
+    event
+}
+
+fn context_provider_event(key: &str) -> Event {
+    let init = EventInit::new();
+    init.set_bubbles(true);
+    init.set_composed(true);
+    let event = Event::new_with_event_init_dict(CONTEXT_PROVIDER, &init).unwrap_throw();
+    set_property(&event, "context", &key.into());
+    event
+}
+
+fn set_property(target: &JsValue, name: &str, value: &JsValue) {
+    Reflect::set(target, &name.into(), value).unwrap_throw();
+}
+
+fn get_property(target: &JsValue, name: &str) -> JsValue {
+    Reflect::get(target, &name.into()).unwrap_or(JsValue::UNDEFINED)
+}
+
+/// Provide a context to the descendant elements, with the [Context Protocol]
+///
+/// The consumers could be other Dioxus web components using [`use_dom_context`],
+/// or any web component implementing the protocol, e.g. a [Lit] `ContextConsumer`.
+/// The subscribed consumers are notified when the value changes,
+/// use a signal, e.g. a `ReadOnlySignal<T>` parameter, to provide a reactive value.
+///
+/// [Context Protocol]: https://github.com/webcomponents-cg/community-protocols/blob/main/proposals/context.md
+/// [Lit]: https://lit.dev/docs/data/context/
+pub fn provide_dom_context<T>(key: &str, value: impl Into<ReadOnlySignal<T>>)
+where
+    T: Clone + Into<JsValue> + 'static,
+{
+    let shared = use_context::<Shared>();
+    let value = value.into();
+    let state = use_hook(|| Rc::new(ProviderState::new(value.peek().clone().into())));
+
+    let listener = use_hook(|| {
+        let key = key.to_string();
+        let host = shared.try_event_target()?;
+        let announce = context_provider_event(&key);
+        let state = Rc::clone(&state);
+        let provider = JsValue::from(host);
+        let listener = Closure::<dyn Fn(Event)>::new(move |event: Event| {
+            if get_property(&event, "context").as_string().as_deref() != Some(&key)
+                || get_property(&event, "contextTarget") == provider
+            {
+                return;
+            }
+            let Ok(callback) = get_property(&event, "callback").dyn_into::<Function>() else {
+                warn!("Invalid context request, missing callback");
+                return;
+            };
+            event.stop_propagation();
+            debug!(%key, "provide context");
+            let subscribe = get_property(&event, "subscribe").is_truthy();
+            state.answer(callback, subscribe);
+        });
+        host.add_event_listener_with_callback(CONTEXT_REQUEST, listener.as_ref().unchecked_ref())
+            .unwrap_throw();
+        // Notify the consumers connected before this provider
+        debug!("announce context provider");
+        host.dispatch_event(&announce).unwrap_throw();
+        Some(Rc::new(listener))
+    });
+
+    use_effect(move || {
+        let new_value = value.read().clone();
+        state.update(new_value.into());
+    });
+
+    use_drop(move || {
+        let (Some(host), Some(listener)) = (shared.try_event_target(), &listener) else {
+            return;
+        };
+        let _ = host.remove_event_listener_with_callback(
+            CONTEXT_REQUEST,
+            listener.as_ref().as_ref().unchecked_ref(),
+        );
+    });
+}
+
+/// The current value and the subscribed consumers of a provided context
+struct ProviderState {
+    value: RefCell<JsValue>,
+    subscribers: Rc<RefCell<Vec<(Function, Function)>>>,
+}
+
+impl ProviderState {
+    fn new(value: JsValue) -> Self {
+        Self {
+            value: RefCell::new(value),
+            subscribers: Rc::default(),
+        }
+    }
+
+    fn answer(&self, callback: Function, subscribe: bool) {
+        let value = self.value.borrow().clone();
+        if !subscribe {
+            let _ = callback.call1(&JsValue::NULL, &value);
+            return;
+        }
+
+        let subscribers = Rc::clone(&self.subscribers);
+        let unsubscribe = Closure::once_into_js({
+            let callback = callback.clone();
+            move || {
+                subscribers
+                    .borrow_mut()
+                    .retain(|(subscriber, _)| *subscriber != callback);
+            }
+        })
+        .unchecked_into::<Function>();
+        let _ = callback.call2(&JsValue::NULL, &value, &unsubscribe);
+        self.subscribers.borrow_mut().push((callback, unsubscribe));
+    }
+
+    fn update(&self, value: JsValue) {
+        if *self.value.borrow() == value {
+            return;
+        }
+        let subscribers = self.subscribers.borrow().clone();
+        for (callback, unsubscribe) in subscribers {
+            let _ = callback.call2(&JsValue::NULL, &value, &unsubscribe);
+        }
+        self.value.replace(value);
+    }
+}
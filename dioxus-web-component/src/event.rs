@@ -3,12 +3,15 @@ use dioxus::prelude::EventHandler;
 use wasm_bindgen::{JsValue, UnwrapThrowExt};
 use web_sys::{CustomEvent, EventTarget};
 
+#[cfg(debug_assertions)]
+use crate::devtools;
+
 /// HTML custom event options
 ///
 /// See [MDN - custom event](https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent)
 ///
 /// Note that by default `can_bubble` & `cancelable` are `true`
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub struct CustomEventOptions {
     /// Is the event bubble up through the DOM tree
     ///
@@ -33,24 +36,36 @@ impl Default for CustomEventOptions {
 /// Create a Dioxus event handler that send an HTML custom event
 pub fn custom_event_handler<T>(
     target: impl AsRef<EventTarget> + 'static,
-    event_type: &'static str,
+    event_type: impl Into<String>,
     options: CustomEventOptions,
 ) -> EventHandler<T>
 where
     T: Into<JsValue> + 'static,
 {
+    let event_type = event_type.into();
     EventHandler::new(move |value: T| {
-        let CustomEventOptions {
-            can_bubble,
-            cancelable,
-        } = options;
-        let event = CustomEvent::new(event_type).unwrap_throw();
         let detail = value.into();
-        event.init_custom_event_with_can_bubble_and_cancelable_and_detail(
-            event_type, can_bubble, cancelable, &detail,
-        );
-        debug!(?event, "dispatch event");
-        let target = target.as_ref();
-        target.dispatch_event(&event).unwrap_throw();
+        dispatch_custom_event(target.as_ref(), &event_type, &detail, options);
     })
 }
+
+/// Dispatch an HTML custom event
+pub fn dispatch_custom_event(
+    target: &EventTarget,
+    event_type: &str,
+    detail: &JsValue,
+    options: CustomEventOptions,
+) {
+    let CustomEventOptions {
+        can_bubble,
+        cancelable,
+    } = options;
+    let event = CustomEvent::new(event_type).unwrap_throw();
+    event.init_custom_event_with_can_bubble_and_cancelable_and_detail(
+        event_type, can_bubble, cancelable, detail,
+    );
+    debug!(?event, "dispatch event");
+    #[cfg(debug_assertions)]
+    devtools::record_event(target, event_type, detail);
+    target.dispatch_event(&event).unwrap_throw();
+}
@@ -0,0 +1,270 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use dioxus::history::{provide_history_context, History};
+use dioxus::logger::tracing::debug;
+use dioxus::prelude::{use_context, use_effect, use_hook, ReadOnlySignal, Readable as _};
+use wasm_bindgen::JsValue;
+
+use crate::{CustomEventOptions, Shared};
+
+/// The custom event dispatched when the component navigates
+const NAVIGATE_EVENT: &str = "navigate";
+
+/// A Dioxus router history scoped to the web component
+///
+/// The history does not use the `window.location`,
+/// the current route is driven by the host (e.g. with a `path` attribute),
+/// and a `navigate` custom event is dispatched when the component navigates,
+/// the event detail is the new path.
+///
+/// See [`use_element_history`].
+pub struct ElementHistory {
+    shared: Shared,
+    state: RefCell<HistoryState>,
+    updater: RefCell<Option<Arc<dyn Fn() + Send + Sync>>>,
+}
+
+#[derive(Debug, Default)]
+struct HistoryState {
+    current: String,
+    past: Vec<String>,
+    future: Vec<String>,
+}
+
+impl HistoryState {
+    fn new(path: String) -> Self {
+        Self {
+            current: normalize(path),
+            ..Self::default()
+        }
+    }
+
+    /// Go to a new path, returns `false` if the path is already the current path
+    fn push(&mut self, path: String) -> bool {
+        let path = normalize(path);
+        if self.current == path {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.current, path);
+        self.past.push(previous);
+        self.future.clear();
+        true
+    }
+
+    fn replace(&mut self, path: String) {
+        self.current = normalize(path);
+    }
+
+    /// Go back, returns `false` if there is no previous path
+    fn go_back(&mut self) -> bool {
+        let Some(previous) = self.past.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.current, previous);
+        self.future.push(current);
+        true
+    }
+
+    /// Go forward, returns `false` if there is no next path
+    fn go_forward(&mut self) -> bool {
+        let Some(next) = self.future.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.current, next);
+        self.past.push(current);
+        true
+    }
+}
+
+impl ElementHistory {
+    fn new(shared: Shared, path: String) -> Self {
+        Self {
+            shared,
+            state: RefCell::new(HistoryState::new(path)),
+            updater: RefCell::default(),
+        }
+    }
+
+    /// Set the path from the host, the `navigate` event is not dispatched
+    fn set_path(&self, path: String) {
+        if !self.state.borrow_mut().push(path) {
+            return;
+        }
+        debug!(path = %self.state.borrow().current, "set the path from the host");
+        if let Some(updater) = self.updater.borrow().as_ref() {
+            updater();
+        }
+    }
+
+    fn navigate(&self) {
+        let path = self.state.borrow().current.clone();
+        debug!(%path, "navigate");
+        self.shared.dispatch_event(
+            NAVIGATE_EVENT,
+            path,
+            CustomEventOptions::default(),
+            JsValue::from,
+        );
+    }
+}
+
+/// The route of the router starts with a `/`
+fn normalize(path: String) -> String {
+    if path.starts_with('/') {
+        path
+    } else {
+        format!("/{path}")
+    }
+}
+
+impl History for ElementHistory {
+    fn current_route(&self) -> String {
+        self.state.borrow().current.clone()
+    }
+
+    fn can_go_back(&self) -> bool {
+        !self.state.borrow().past.is_empty()
+    }
+
+    fn go_back(&self) {
+        if self.state.borrow_mut().go_back() {
+            self.navigate();
+        }
+    }
+
+    fn can_go_forward(&self) -> bool {
+        !self.state.borrow().future.is_empty()
+    }
+
+    fn go_forward(&self) {
+        if self.state.borrow_mut().go_forward() {
+            self.navigate();
+        }
+    }
+
+    fn push(&self, route: String) {
+        if self.state.borrow_mut().push(route) {
+            self.navigate();
+        }
+    }
+
+    fn replace(&self, path: String) {
+        self.state.borrow_mut().replace(path);
+        self.navigate();
+    }
+
+    fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {
+        self.updater.replace(Some(callback));
+    }
+
+    fn include_prevent_default(&self) -> bool {
+        // Links should not change the host page location
+        true
+    }
+}
+
+/// Provide an [`ElementHistory`] to the Dioxus router rendered by the component
+///
+/// The router is driven by the `path` signal, e.g. a `path` attribute of the web component,
+/// instead of the `window.location`.
+/// When the component navigates, a `navigate` custom event is dispatched with the new path,
+/// so the host page can update its own routing.
+///
+/// The hook should be called by a parent of the `Router` component.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{use_element_history, web_component};
+///
+/// #[derive(Clone, Routable, Debug, PartialEq)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/settings")]
+///     Settings {},
+/// }
+///
+/// # #[component]
+/// # fn Home() -> Element { rsx! {} }
+/// # #[component]
+/// # fn Settings() -> Element { rsx! {} }
+/// #[web_component(tag = "plop-app")]
+/// fn App(#[attribute(initial = "/".to_string())] path: ReadOnlySignal<String>) -> Element {
+///     use_element_history(path);
+///     rsx! { Router::<Route> {} }
+/// }
+/// ```
+pub fn use_element_history(path: impl Into<ReadOnlySignal<String>>) -> Rc<ElementHistory> {
+    let shared = use_context::<Shared>();
+    let path = path.into();
+
+    let history = use_hook(|| {
+        let history = Rc::new(ElementHistory::new(shared.clone(), path.peek().clone()));
+        provide_history_context(Rc::clone(&history) as Rc<dyn History>);
+        history
+    });
+
+    use_effect({
+        let history = Rc::clone(&history);
+        move || {
+            let path = path.read().clone();
+            history.set_path(path);
+        }
+    });
+
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_normalize_initial_path() {
+        let state = HistoryState::new("settings".to_string());
+        assert_eq!(state.current, "/settings");
+    }
+
+    #[test]
+    fn should_normalize_pushed_path() {
+        let mut state = HistoryState::new("/".to_string());
+        assert!(state.push("settings".to_string()));
+        assert_eq!(state.current, "/settings");
+        assert_eq!(state.past, vec!["/"]);
+
+        // Same route, with or without the leading `/`
+        assert!(!state.push("/settings".to_string()));
+        assert_eq!(state.past, vec!["/"]);
+    }
+
+    #[test]
+    fn should_normalize_replaced_path() {
+        let mut state = HistoryState::new("/".to_string());
+        state.replace("settings".to_string());
+        assert_eq!(state.current, "/settings");
+        assert!(state.past.is_empty());
+    }
+
+    #[test]
+    fn should_go_back_and_forward() {
+        let mut state = HistoryState::new("/".to_string());
+        state.push("/a".to_string());
+        state.push("/b".to_string());
+
+        assert!(state.go_back());
+        assert_eq!(state.current, "/a");
+        assert!(state.go_back());
+        assert_eq!(state.current, "/");
+        assert!(!state.go_back());
+
+        assert!(state.go_forward());
+        assert_eq!(state.current, "/a");
+
+        // A new path clears the forward history
+        state.push("/c".to_string());
+        assert!(!state.go_forward());
+        assert_eq!(state.past, vec!["/", "/a"]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::dioxus_core::{Runtime, ScopeId, VirtualDom};
+use dioxus::logger::tracing::{debug, info, warn};
+use dioxus::signals::{get_global_context, warnings, GlobalKey, Writable as _};
+use dioxus::warnings::Warning as _;
+use dioxus_devtools_types::{DevserverMsg, HotReloadMsg};
+use js_sys::{Date, JsString};
+use wasm_bindgen::prelude::*;
+use web_sys::{window, CloseEvent, Document, HtmlLinkElement, MessageEvent, ShadowRoot, WebSocket};
+
+const RECONNECT_DELAY_MIN: i32 = 250;
+const RECONNECT_DELAY_MAX: i32 = 4_000;
+
+thread_local! {
+    static HOT_RELOAD: RefCell<Vec<HotReloadApp>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The root where the styles are injected
+pub(crate) enum StyleRoot {
+    Shadow(ShadowRoot),
+    Document(Document),
+}
+
+/// A Dioxus application receiving the hot-reload changes
+struct HotReloadApp {
+    id: usize,
+    runtime: Rc<Runtime>,
+    style_root: StyleRoot,
+}
+
+/// Connect the web components to the hot-reload channel of the Dioxus devserver (debug builds only)
+///
+/// The devserver is started with `dx serve`, the endpoint is usually `ws://127.0.0.1:8080/_dioxus`.
+/// It's useful when the web components are embedded in a page not served by the devserver,
+/// e.g. a plain HTML page, or a React application.
+///
+/// The `rsx!` changes are applied to all the instances, without reloading the page.
+/// The stylesheets injected with [`InjectedStyle::stylesheet`](crate::InjectedStyle::stylesheet) are reloaded
+/// when an asset changes.
+/// The page is reloaded after a full rebuild.
+///
+/// ⚠️ The inline CSS of [`InjectedStyle::css`](crate::InjectedStyle::css) is not hot-reloaded,
+/// the CSS text is compiled into the wasm module, so a change triggers a full rebuild and a page reload.
+///
+/// Call this function once, before adding the web components to the page.
+///
+/// ```rust, no_run
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen(start)]
+/// pub fn register() {
+///     #[cfg(debug_assertions)]
+///     dioxus_web_component::connect_devserver("ws://127.0.0.1:8080/_dioxus");
+///     // register the web components
+/// }
+/// ```
+pub fn connect_devserver(endpoint: &str) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    connect(endpoint.to_string(), RECONNECT_DELAY_MIN);
+}
+
+fn connect(endpoint: String, delay: i32) {
+    debug!(%endpoint, "connect to the devserver");
+    let Ok(websocket) = WebSocket::new(&endpoint) else {
+        warn!(%endpoint, "Invalid devserver endpoint");
+        return;
+    };
+
+    let on_message = Closure::<dyn Fn(MessageEvent)>::new(|event: MessageEvent| {
+        let Ok(text) = event.data().dyn_into::<JsString>() else {
+            return;
+        };
+        let text = String::from(text);
+        match serde_json::from_str::<DevserverMsg>(&text) {
+            Ok(DevserverMsg::HotReload(message)) => apply(&message),
+            Ok(DevserverMsg::FullReloadStart) => {
+                info!("The devserver is rebuilding the application");
+            }
+            Ok(DevserverMsg::FullReloadFailed) => {
+                warn!("The devserver fails to rebuild the application");
+            }
+            Ok(DevserverMsg::FullReloadCommand) => {
+                if let Some(window) = window() {
+                    let _ = window.location().reload();
+                }
+            }
+            Ok(DevserverMsg::Shutdown) => warn!("The devserver is shutting down"),
+            Err(error) => warn!(%error, "Invalid devserver message"),
+        }
+    });
+    websocket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+
+    // Reconnect, e.g. when the devserver restarts
+    let on_close = Closure::once_into_js(move |event: CloseEvent| {
+        // Closed because the page is reloaded
+        if event.code() == 1001 {
+            return;
+        }
+        let next_delay = (delay * 2).min(RECONNECT_DELAY_MAX);
+        let reconnect = Closure::once_into_js(move || connect(endpoint, next_delay));
+        if let Some(window) = window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                reconnect.unchecked_ref(),
+                delay,
+            );
+        }
+    });
+    websocket.set_onclose(Some(on_close.unchecked_ref()));
+}
+
+/// Receive the hot-reload changes
+pub(crate) fn register(id: usize, vdom: &VirtualDom, style_root: StyleRoot) {
+    let app = HotReloadApp {
+        id,
+        runtime: vdom.runtime(),
+        style_root,
+    };
+    HOT_RELOAD.with_borrow_mut(|apps| {
+        apps.retain(|it| it.id != id);
+        apps.push(app);
+    });
+}
+
+/// Stop receiving the hot-reload changes
+pub(crate) fn unregister(id: usize) {
+    HOT_RELOAD.with_borrow_mut(|apps| apps.retain(|it| it.id != id));
+}
+
+fn apply(message: &HotReloadMsg) {
+    debug!(templates = message.templates.len(), assets = ?message.assets, "hot-reload");
+    HOT_RELOAD.with_borrow(|apps| {
+        for app in apps {
+            apply_templates(&app.runtime, message);
+            if !message.assets.is_empty() {
+                reload_stylesheets(&app.style_root);
+            }
+        }
+    });
+}
+
+/// Replace the `rsx!` templates, like `dioxus_devtools::apply_changes`
+fn apply_templates(runtime: &Rc<Runtime>, message: &HotReloadMsg) {
+    runtime.on_scope(ScopeId::ROOT, || {
+        let context = get_global_context();
+        for template in &message.templates {
+            let key = &template.key;
+            let (Ok(line), Ok(column), Ok(index)) = (
+                u32::try_from(key.line),
+                u32::try_from(key.column),
+                u32::try_from(key.index),
+            ) else {
+                continue;
+            };
+            let key = GlobalKey::File {
+                file: key.file.as_str(),
+                line,
+                column,
+                index,
+            };
+            if let Some(mut signal) = context.get_signal_with_key(key) {
+                warnings::signal_read_and_write_in_reactive_scope::allow(|| {
+                    warnings::signal_write_in_component_body::allow(|| {
+                        signal.set(Some(template.template.clone()));
+                    });
+                });
+            }
+        }
+    });
+}
+
+/// Force the browser to fetch the stylesheets again
+fn reload_stylesheets(style_root: &StyleRoot) {
+    let links = match style_root {
+        StyleRoot::Shadow(root) => root.query_selector_all("link[rel=stylesheet]"),
+        StyleRoot::Document(document) => document.query_selector_all("link[rel=stylesheet]"),
+    };
+    let Ok(links) = links else {
+        return;
+    };
+    let timestamp = Date::now();
+    for index in 0..links.length() {
+        let Some(link) = links
+            .item(index)
+            .and_then(|node| node.dyn_into::<HtmlLinkElement>().ok())
+        else {
+            continue;
+        };
+        let href = link.href();
+        let base = href.split('?').next().unwrap_or(&href);
+        link.set_href(&format!("{base}?dx_reload={timestamp}"));
+    }
+}
@@ -1,31 +1,86 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::multiple_crate_versions)]
 
+use std::any::Any;
 use std::sync::Arc;
 use std::sync::RwLock;
 
 use dioxus::dioxus_core::Element;
 use dioxus::hooks::UnboundedSender;
-use dioxus::logger::tracing::debug;
+use dioxus::logger::tracing::{debug, warn};
+use dioxus::prelude::{queue_effect, EventHandler};
 use futures::channel::oneshot;
 use wasm_bindgen::prelude::*;
 use web_sys::HtmlElement;
 
 use crate::rust_component::RustComponent;
 
+#[cfg(feature = "macros")]
 pub use dioxus_web_component_macro::web_component;
 
+pub use dioxus_web_component_tag::{check_tag, CustomElementName, InvalidTagError};
+
 mod event;
 pub use self::event::*;
 
 mod style;
 pub use self::style::*;
 
+mod model;
+pub use self::model::*;
+
+mod builder;
+pub use self::builder::*;
+
+mod deferred;
+pub use self::deferred::*;
+
+mod registry;
+pub use self::registry::*;
+
+mod context;
+pub use self::context::provide_global_context;
+
+mod aria;
+pub use self::aria::{use_aria, Aria, ElementInternals};
+
+mod custom_state;
+pub use self::custom_state::use_custom_state;
+
+mod parts;
+pub use self::parts::export_parts;
+
+mod dom_context;
+pub use self::dom_context::*;
+
+mod history;
+pub use self::history::*;
+
+mod logging;
+
+#[cfg(debug_assertions)]
+mod devtools;
+
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+#[cfg(feature = "hot-reload")]
+pub use self::hot_reload::connect_devserver;
+
 mod rust_component;
 
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(feature = "testing")]
+pub use self::testing::*;
+
+mod shared_runtime;
+
 /// Re-export, use this trait in the coroutine
 pub use futures::StreamExt;
 
+/// Re-export, use this type for the scoped custom element registries
+pub use web_sys::CustomElementRegistry;
+
 /// Message from web component to dioxus
 #[derive(Debug)]
 #[non_exhaustive]
@@ -51,6 +106,38 @@ pub enum Message {
         /// Property value
         value: SharedJsValue,
     },
+    /// Set property with a Rust value, used by the native test mode
+    SetValue {
+        /// Property name
+        name: String,
+        /// Property value
+        value: Box<dyn Any + Send>,
+    },
+    /// Wait for the render of the previous messages
+    Flush {
+        /// notified after the next render
+        tx: oneshot::Sender<()>,
+    },
+}
+
+/// The target of an update message
+#[derive(Debug, PartialEq, Eq)]
+enum UpdateTarget<'a> {
+    Attribute(&'a str),
+    Property(&'a str),
+}
+
+impl Message {
+    /// The attribute, or the property, updated by the message
+    fn update_target(&self) -> Option<UpdateTarget<'_>> {
+        match self {
+            Self::SetAttribute { name, .. } => Some(UpdateTarget::Attribute(name)),
+            Self::Set { name, .. } | Self::SetValue { name, .. } => {
+                Some(UpdateTarget::Property(name))
+            }
+            Self::Get { .. } | Self::Flush { .. } => None,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -68,6 +155,19 @@ unsafe impl Send for SharedEventTarget {}
 // This only be used to display an event, no update are made here
 unsafe impl Sync for SharedEventTarget {}
 
+#[derive(Clone)]
+struct SharedElementInternals(ElementInternals);
+
+#[allow(unsafe_code)]
+// SAFETY:
+// In a Web WASM context, without thread.
+unsafe impl Send for SharedElementInternals {}
+
+#[allow(unsafe_code)]
+// SAFETY:
+// In a Web WASM context, without thread.
+unsafe impl Sync for SharedElementInternals {}
+
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct SharedJsValue(JsValue);
@@ -84,38 +184,200 @@ unsafe impl Send for SharedJsValue {}
 // This only be used to display an event, no update are made here
 unsafe impl Sync for SharedJsValue {}
 
+/// The channel to the component, the messages are queued until the component is ready
+///
+/// So the attributes and properties set before the connection are not lost.
+#[derive(Default)]
+pub(crate) struct MessageChannel {
+    tx: Option<UnboundedSender<Message>>,
+    /// The host is connected, the component is ready after its first render
+    host_connected: bool,
+    queued: Vec<Message>,
+}
+
+impl MessageChannel {
+    /// The sender, if the component is ready
+    #[cfg(debug_assertions)]
+    pub(crate) fn sender(&self) -> Option<&UnboundedSender<Message>> {
+        self.tx.as_ref()
+    }
+
+    /// The host is connected, queue the messages until the component is ready
+    pub(crate) fn connect_host(&mut self) {
+        self.host_connected = true;
+    }
+
+    /// Send the message, or queue it until the component is ready
+    ///
+    /// A property read is not queued, the value is `undefined` before the connection.
+    /// A flush is only queued while the host is connected, otherwise there is no render to wait for.
+    /// Only the last value of each attribute and property is queued,
+    /// so a disconnected component receiving many updates does not grow the queue.
+    pub(crate) fn send(&mut self, message: Message) {
+        match &self.tx {
+            Some(tx) => {
+                let _ = tx.unbounded_send(message);
+            }
+            None if matches!(message, Message::Get { .. }) => {
+                debug!(?message, "Component not ready");
+            }
+            None if matches!(message, Message::Flush { .. }) && !self.host_connected => {
+                debug!(?message, "Component disconnected");
+            }
+            None => {
+                if let Some(target) = message.update_target() {
+                    self.queued
+                        .retain(|queued| queued.update_target().as_ref() != Some(&target));
+                }
+                self.queued.push(message);
+            }
+        }
+    }
+
+    /// Send the queued messages, then keep the sender
+    fn connect(&mut self, tx: UnboundedSender<Message>) {
+        for message in self.queued.drain(..) {
+            let _ = tx.unbounded_send(message);
+        }
+        self.tx = Some(tx);
+    }
+
+    /// Queue the next messages, the queued flushes are settled without render
+    pub(crate) fn disconnect(&mut self) {
+        self.tx = None;
+        self.host_connected = false;
+        self.queued
+            .retain(|message| !matches!(message, Message::Flush { .. }));
+    }
+}
+
+/// The host of the web component
+#[derive(Clone)]
+enum Host {
+    Element(SharedEventTarget),
+    #[cfg(feature = "testing")]
+    Test(testing::SharedTestHost),
+}
+
 /// A context provided by the web component
 #[derive(Clone)]
 pub struct Shared {
     attributes: Vec<String>,
-    event_target: SharedEventTarget,
-    tx: Arc<RwLock<Option<UnboundedSender<Message>>>>,
+    host: Host,
+    internals: Option<SharedElementInternals>,
+    tx: Arc<RwLock<MessageChannel>>,
 }
 
 impl Shared {
     /// The web component event target use to dispatch custom event
+    ///
+    /// Prefer [`Self::dispatch_event`] and [`Self::reflect_attribute`], also available in the native test mode.
+    ///
+    /// # Panics
+    ///
+    /// In the native test mode, where the host element is simulated, see [`Self::try_event_target`].
     #[must_use]
     pub fn event_target(&self) -> &HtmlElement {
-        &self.event_target.0
+        self.try_event_target()
+            .expect_throw("no host element in the native test mode")
+    }
+
+    /// The web component event target, `None` in the native test mode
+    #[must_use]
+    pub fn try_event_target(&self) -> Option<&HtmlElement> {
+        match &self.host {
+            Host::Element(target) => Some(&target.0),
+            #[cfg(feature = "testing")]
+            Host::Test(_) => None,
+        }
+    }
+
+    /// The simulated host element of the native test mode, `None` in a browser
+    #[cfg(feature = "testing")]
+    #[must_use]
+    pub fn test_host(&self) -> Option<&TestHost> {
+        match &self.host {
+            Host::Element(_) => None,
+            Host::Test(host) => Some(&host.0),
+        }
+    }
+
+    /// The web component `ElementInternals`, if supported by the browser
+    #[must_use]
+    pub fn internals(&self) -> Option<&ElementInternals> {
+        self.internals.as_ref().map(|it| &it.0)
+    }
+
+    /// Create an handler that dispatch a custom event from the host
+    #[must_use]
+    pub fn event_handler<T>(&self, event_type: &str, options: CustomEventOptions) -> EventHandler<T>
+    where
+        T: Into<JsValue> + 'static,
+    {
+        let shared = self.clone();
+        let event_type = event_type.to_string();
+        EventHandler::new(move |value: T| {
+            shared.dispatch_event(&event_type, value, options, Into::into);
+        })
+    }
+
+    /// Dispatch a custom event from the host
+    ///
+    /// The detail is converted into a `JsValue` with `into_js`.
+    pub fn dispatch_event<T>(
+        &self,
+        event_type: &str,
+        detail: T,
+        options: CustomEventOptions,
+        into_js: impl FnOnce(T) -> JsValue,
+    ) where
+        T: 'static,
+    {
+        match &self.host {
+            Host::Element(target) => {
+                let detail = into_js(detail);
+                dispatch_custom_event(&target.0, event_type, &detail, options);
+            }
+            #[cfg(feature = "testing")]
+            Host::Test(host) => host.0.dispatch(event_type, detail, options),
+        }
+    }
+
+    /// Reflect a value into an HTML attribute of the host
+    ///
+    /// A `None` value removes the attribute.
+    pub fn reflect_attribute(&self, name: &str, value: Option<&str>) {
+        match &self.host {
+            Host::Element(target) => reflect_attribute(&target.0, name, value),
+            #[cfg(feature = "testing")]
+            Host::Test(host) => host.0.set_attribute(name, value),
+        }
+    }
+
+    fn initial_attribute(&self, name: &str) -> Option<String> {
+        match &self.host {
+            Host::Element(target) => target.0.get_attribute(name),
+            #[cfg(feature = "testing")]
+            Host::Test(host) => host.0.attribute(name),
+        }
     }
 
     /// Set the receiver
     pub fn set_tx(&mut self, tx: UnboundedSender<Message>) {
         // initial state
-        let trg = self.event_target();
         for attr in &self.attributes {
-            let Some(value) = trg.get_attribute(attr) else {
+            let Some(value) = self.initial_attribute(attr) else {
                 continue;
             };
             let _ = tx.unbounded_send(Message::SetAttribute {
-                name: attr.to_string(),
+                name: attr.clone(),
                 value: Some(value),
             });
         }
 
-        // Keep sender (skip if poisoned)
-        if let Ok(mut cell) = self.tx.write() {
-            *cell = Some(tx);
+        // Send the queued messages, and keep the sender (skip if poisoned)
+        if let Ok(mut channel) = self.tx.write() {
+            channel.connect(tx);
         }
     }
 }
@@ -134,6 +396,12 @@ pub trait DioxusWebComponent {
         let _ = property;
     }
 
+    /// Set a property with a Rust value, used by the native test mode
+    fn set_property_value(&mut self, property: &str, value: Box<dyn Any + Send>) {
+        let _ = value;
+        warn!("No property {property} to set");
+    }
+
     /// Get a property
     fn get_property(&mut self, property: &str) -> JsValue {
         let _ = property;
@@ -143,6 +411,8 @@ pub trait DioxusWebComponent {
     /// Handle a message
     fn handle_message(&mut self, message: Message) {
         debug!(?message, "handle message");
+        #[cfg(debug_assertions)]
+        devtools::record_message(&message);
         match message {
             Message::SetAttribute { name, value } => self.set_attribute(&name, value),
             Message::Get { name, tx } => {
@@ -150,10 +420,27 @@ pub trait DioxusWebComponent {
                 let _ = tx.send(SharedJsValue(value));
             }
             Message::Set { name, value } => self.set_property(&name, value.0),
+            Message::SetValue { name, value } => self.set_property_value(&name, value),
+            Message::Flush { tx } => queue_effect(move || {
+                let _ = tx.send(());
+            }),
         }
     }
 }
 
+/// The Dioxus root of a web component, implemented by the `#[web_component]` macro
+///
+/// The root reads the [`Shared`] context, and renders the component with the received values.
+/// With the `testing` feature, use `NativeWebComponent::of` to render it without a browser.
+pub trait WebComponentRoot {
+    /// Render an instance of the web component
+    ///
+    /// # Errors
+    ///
+    /// Like any Dioxus component, the render fails if the component fails.
+    fn root() -> Element;
+}
+
 /// Property
 #[wasm_bindgen(skip_typescript)]
 #[derive(Debug, Clone)]
@@ -190,6 +477,9 @@ impl Property {
 }
 
 /// Register a Dioxus web component
+///
+/// The registration is skipped if the tag is invalid or already used,
+/// see [`WebComponentBuilder::try_register_with_builder`].
 pub fn register_dioxus_web_component(
     custom_tag: &str,
     attributes: Vec<String>,
@@ -197,17 +487,113 @@ pub fn register_dioxus_web_component(
     style: InjectedStyle,
     dx_el_builder: fn() -> Element,
 ) {
-    let rust_component = RustComponent {
-        attributes,
-        properties,
-        style,
-        dx_el_builder,
-    };
-    register_web_component(custom_tag, rust_component);
+    let builder = attributes.into_iter().fold(
+        WebComponentBuilder::new(custom_tag),
+        WebComponentBuilder::attribute,
+    );
+    let builder = properties.into_iter().fold(builder, |builder, property| {
+        builder.property(property.name, property.readonly)
+    });
+    builder.style(style).register_with_builder(dx_el_builder);
 }
 
 #[wasm_bindgen(module = "/src/shim.js")]
 extern "C" {
     #[allow(unsafe_code)]
-    fn register_web_component(custom_tag: &str, rust_component: RustComponent);
+    fn register_web_component(
+        custom_tag: &str,
+        rust_component: RustComponent,
+        custom_element_registry: Option<&CustomElementRegistry>,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::mpsc::unbounded;
+    use futures::FutureExt as _;
+
+    use super::*;
+
+    fn set_attribute(name: &str, value: &str) -> Message {
+        Message::SetAttribute {
+            name: name.to_string(),
+            value: Some(value.to_string()),
+        }
+    }
+
+    fn set_value(name: &str, value: i32) -> Message {
+        Message::SetValue {
+            name: name.to_string(),
+            value: Box::new(value),
+        }
+    }
+
+    #[test]
+    fn should_queue_last_value_while_disconnected() {
+        let mut channel = MessageChannel::default();
+        channel.connect_host();
+        for index in 0..100 {
+            channel.send(set_attribute("label", &index.to_string()));
+            channel.send(set_value("step", index));
+        }
+        let (flush, _) = oneshot::channel();
+        channel.send(Message::Flush { tx: flush });
+        channel.send(set_value("label", 42));
+
+        let (tx, mut rx) = unbounded();
+        channel.connect(tx);
+        let mut received = vec![];
+        while let Ok(message) = rx.try_recv() {
+            received.push(message);
+        }
+        let received = received
+            .iter()
+            .map(|message| match message {
+                Message::SetAttribute { name, value } => {
+                    format!("attribute {name}={}", value.as_deref().unwrap_or_default())
+                }
+                Message::SetValue { name, value } => {
+                    format!("property {name}={:?}", value.downcast_ref::<i32>())
+                }
+                Message::Flush { .. } => "flush".to_string(),
+                _ => "unexpected".to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            received,
+            vec![
+                "attribute label=99",
+                "property step=Some(99)",
+                "flush",
+                "property label=Some(42)",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_bound_queue_while_disconnected() {
+        let mut channel = MessageChannel::default();
+        for index in 0..100 {
+            channel.send(set_attribute("label", &index.to_string()));
+            channel.send(set_value("step", index));
+            let (flush, rx) = oneshot::channel();
+            channel.send(Message::Flush { tx: flush });
+            // Settled, nothing to render
+            assert!(matches!(rx.now_or_never(), Some(Err(_))));
+        }
+        assert_eq!(channel.queued.len(), 2);
+    }
+
+    #[test]
+    fn should_settle_queued_flush_on_disconnect() {
+        let mut channel = MessageChannel::default();
+        channel.connect_host();
+        let (flush, rx) = oneshot::channel();
+        channel.send(Message::Flush { tx: flush });
+        channel.send(set_value("step", 1));
+
+        channel.disconnect();
+        assert!(matches!(rx.now_or_never(), Some(Err(_))));
+        assert_eq!(channel.queued.len(), 1);
+    }
 }
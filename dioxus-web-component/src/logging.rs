@@ -0,0 +1,128 @@
+use std::future::Future;
+
+use dioxus::logger::tracing::dispatcher::{self, DefaultGuard};
+use dioxus::logger::tracing::instrument::WithSubscriber as _;
+use dioxus::logger::tracing::level_filters::LevelFilter;
+use dioxus::logger::tracing::span::{Attributes, Entered, Id, Record};
+use dioxus::logger::tracing::subscriber::Interest;
+use dioxus::logger::tracing::{field, info_span, Dispatch, Event, Instrument as _, Level};
+use dioxus::logger::tracing::{Metadata, Span, Subscriber};
+use futures::future::Either;
+
+/// The tracing context of a web component instance
+///
+/// All the logs of the instance, the runtime logs and the Dioxus application logs,
+/// are recorded in a `web_component` span with the `tag`, the `instance` id,
+/// and the `id` attribute of the host (if any).
+/// With a log level for the tag, the more verbose logs of the instance are skipped.
+#[derive(Clone)]
+pub(crate) struct InstanceTracing {
+    span: Span,
+    dispatch: Option<Dispatch>,
+}
+
+impl InstanceTracing {
+    pub(crate) fn new(tag: &str, instance: usize, log_level: Option<Level>) -> Self {
+        let span = info_span!("web_component", tag, instance, id = field::Empty);
+        let dispatch = log_level.map(|level| {
+            let inner = dispatcher::get_default(Clone::clone);
+            Dispatch::new(LevelCap {
+                inner,
+                max_level: LevelFilter::from_level(level),
+            })
+        });
+        Self { span, dispatch }
+    }
+
+    /// Record the `id` attribute of the host
+    pub(crate) fn record_id(&self, id: Option<&str>) {
+        if let Some(id) = id {
+            self.span.record("id", id);
+        }
+    }
+
+    /// Enter the instance span, the logs are filtered with the tag log level until the guard is dropped
+    pub(crate) fn enter(&self) -> InstanceGuard<'_> {
+        let dispatch = self.dispatch.as_ref().map(dispatcher::set_default);
+        let entered = self.span.enter();
+        InstanceGuard {
+            _entered: entered,
+            _dispatch: dispatch,
+        }
+    }
+
+    /// Run the future in the instance span
+    pub(crate) fn instrument_future<F>(&self, future: F) -> impl Future<Output = F::Output>
+    where
+        F: Future,
+    {
+        let future = future.instrument(self.span.clone());
+        match &self.dispatch {
+            Some(dispatch) => Either::Left(future.with_subscriber(dispatch.clone())),
+            None => Either::Right(future),
+        }
+    }
+}
+
+/// See [`InstanceTracing::enter`]
+pub(crate) struct InstanceGuard<'a> {
+    // Drop order matters, exit the span before restoring the dispatcher
+    _entered: Entered<'a>,
+    _dispatch: Option<DefaultGuard>,
+}
+
+/// Skip the spans and events more verbose than the max level, then delegate to the inner subscriber
+struct LevelCap {
+    inner: Dispatch,
+    max_level: LevelFilter,
+}
+
+impl Subscriber for LevelCap {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if *metadata.level() > self.max_level {
+            Interest::never()
+        } else {
+            self.inner.register_callsite(metadata)
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(self.max_level)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.max_level && self.inner.enabled(metadata)
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.inner.new_span(span)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        self.inner.record(span, values);
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        self.inner.record_follows_from(span, follows);
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        self.inner.event(event);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.inner.enter(span);
+    }
+
+    fn exit(&self, span: &Id) {
+        self.inner.exit(span);
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.inner.clone_span(id)
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        self.inner.try_close(id)
+    }
+}
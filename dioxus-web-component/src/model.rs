@@ -0,0 +1,87 @@
+use std::ops::Deref;
+
+use dioxus::prelude::{use_effect, use_signal, Readable as _, Signal, Writable as _};
+use wasm_bindgen::UnwrapThrowExt as _;
+use web_sys::Element;
+
+/// A value shared between the host and the component (two-way binding)
+///
+/// The component writes into the [`Model::signal`],
+/// and the host is notified with the callback provided to [`use_model`].
+/// Values set by the host (attribute or property) do not notify the host back.
+pub struct Model<T: 'static> {
+    value: Signal<T>,
+    synced: Signal<T>,
+}
+
+impl<T> Clone for Model<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Model<T> {}
+
+impl<T> Model<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    /// Set the value from the host side
+    pub fn set(&mut self, value: T) {
+        self.synced.set(value.clone());
+        self.value.set(value);
+    }
+
+    /// The writable signal given to the component
+    #[must_use]
+    pub fn signal(&self) -> Signal<T> {
+        self.value
+    }
+}
+
+impl<T> Deref for Model<T> {
+    type Target = Signal<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Create a [`Model`]
+///
+/// The `on_change` callback is called when the component changes the value.
+pub fn use_model<T>(initial: impl FnOnce() -> T, mut on_change: impl FnMut(T) + 'static) -> Model<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let value = use_signal(initial);
+    let mut synced = use_signal(|| value.peek().clone());
+
+    use_effect(move || {
+        let current = value.read().clone();
+        if *synced.peek() != current {
+            synced.set(current.clone());
+            on_change(current);
+        }
+    });
+
+    Model { value, synced }
+}
+
+/// Reflect a value into an HTML attribute of the host
+///
+/// A `None` value removes the attribute.
+pub fn reflect_attribute(host: &Element, name: &str, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            if host.get_attribute(name).as_deref() != Some(value) {
+                host.set_attribute(name, value).unwrap_throw();
+            }
+        }
+        None => {
+            if host.has_attribute(name) {
+                host.remove_attribute(name).unwrap_throw();
+            }
+        }
+    }
+}
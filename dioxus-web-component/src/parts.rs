@@ -0,0 +1,84 @@
+use dioxus::logger::tracing::warn;
+use futures::channel::oneshot;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::Element;
+
+use crate::registry::{prefixed_tag, registered_components};
+use crate::Message;
+
+/// The `exportparts` attribute value to forward the CSS parts of a nested web component
+///
+/// The parts of a web component rendered inside the shadow root of another web component
+/// are not reachable from the page, unless they are forwarded with the `exportparts` attribute.
+/// The tag should be registered by this module, the forwarded parts are the declared `parts`.
+///
+/// With a prefix, the parts are renamed, e.g. `label: icon-label` with the `icon` prefix.
+///
+/// See [MDN - exportparts](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/exportparts)
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{export_parts, web_component};
+///
+/// #[web_component(tag = "plop-icon", parts = ["svg"])]
+/// fn Icon() -> Element {
+///     rsx! { svg { "part": "svg" } }
+/// }
+///
+/// #[web_component(tag = "plop-button", parts = ["label"])]
+/// fn Button() -> Element {
+///     rsx! {
+///         plop-icon { "exportparts": export_parts("plop-icon", Some("icon")) }
+///         span { "part": "label", "Click" }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn export_parts(tag: &str, prefix: Option<&str>) -> String {
+    let tag = prefixed_tag(tag);
+    let Some(component) = registered_components()
+        .into_iter()
+        .find(|component| component.tag == tag)
+    else {
+        warn!(%tag, "Cannot export the parts of an unregistered web component");
+        return String::new();
+    };
+
+    component
+        .parts
+        .iter()
+        .map(|part| match prefix {
+            Some(prefix) => format!("{part}: {prefix}-{part}"),
+            None => part.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Warn when a declared CSS part is not in the rendered template (debug builds only)
+///
+/// The check is done when the returned message is flushed, after the next render of the instance.
+/// A part rendered later, e.g. after an async load, is reported as missing.
+pub(crate) fn check_parts(tag: &str, root: &Element, parts: &[String]) -> Option<Message> {
+    if !cfg!(debug_assertions) || parts.is_empty() {
+        return None;
+    }
+
+    let tag = tag.to_string();
+    let root = root.clone();
+    let parts = parts.to_vec();
+    let (tx, rx) = oneshot::channel();
+    spawn_local(async move {
+        // Cancelled if disconnected before the render
+        if rx.await.is_err() {
+            return;
+        }
+        for part in &parts {
+            let selector = format!("[part~=\"{part}\"]");
+            if !matches!(root.query_selector(&selector), Ok(Some(_))) {
+                warn!(%tag, %part, "The declared CSS part is not found in the rendered template");
+            }
+        }
+    });
+    Some(Message::Flush { tx })
+}
@@ -0,0 +1,246 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use dioxus_web_component_tag::{check_tag, InvalidTagError};
+use wasm_bindgen::UnwrapThrowExt as _;
+use web_sys::{window, CustomElementRegistry};
+
+use crate::{CustomEventOptions, InjectedStyle, Property};
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    prefix: Option<String>,
+    components: Vec<RegisteredComponent>,
+}
+
+/// A web component registered by this module
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RegisteredComponent {
+    /// The custom element tag, including the prefix
+    pub tag: String,
+    /// The HTML attributes
+    pub attributes: Vec<String>,
+    /// The Javascript properties
+    pub properties: Vec<Property>,
+    /// The custom events
+    pub events: Vec<(String, CustomEventOptions)>,
+    /// The style
+    pub style: InjectedStyle,
+    /// Is the component rendered with the shared runtime
+    pub shared_runtime: bool,
+    /// The default ARIA role
+    pub role: Option<String>,
+    /// The declared custom states
+    pub states: Vec<String>,
+    /// The declared CSS parts
+    pub parts: Vec<String>,
+    /// The declared CSS custom properties
+    pub css_properties: Vec<String>,
+    /// Are the lifecycle events dispatched
+    pub lifecycle_events: bool,
+    /// The scoped custom element registry, `None` for the global `customElements`
+    pub custom_element_registry: Option<CustomElementRegistry>,
+}
+
+/// Error when registering a web component
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegistrationError {
+    /// The tag is not a valid custom element name
+    InvalidTag(InvalidTagError),
+    /// The tag is already registered by this module (in the same custom element registry)
+    AlreadyRegistered(String),
+    /// The tag is already defined in the custom element registry, e.g. by another module
+    AlreadyDefined(String),
+}
+
+impl Display for RegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTag(error) => write!(f, "{error}"),
+            Self::AlreadyRegistered(tag) => {
+                write!(f, "the <{tag}> web component is already registered")
+            }
+            Self::AlreadyDefined(tag) => write!(f, "the <{tag}> custom element is already defined"),
+        }
+    }
+}
+
+impl Error for RegistrationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidTag(error) => Some(error),
+            Self::AlreadyRegistered(_) | Self::AlreadyDefined(_) => None,
+        }
+    }
+}
+
+impl From<InvalidTagError> for RegistrationError {
+    fn from(error: InvalidTagError) -> Self {
+        Self::InvalidTag(error)
+    }
+}
+
+/// All the web components registered by this module
+#[must_use]
+pub fn registered_components() -> Vec<RegisteredComponent> {
+    REGISTRY.with_borrow(|registry| registry.components.clone())
+}
+
+/// Set a prefix applied to the tags of the web components registered after this call
+///
+/// For example, with the `acme` prefix the `plop-counter` web component is registered
+/// with the `acme-plop-counter` tag.
+/// It avoids collisions when multiple modules (micro-frontends) register the same components.
+pub fn set_tag_prefix(prefix: impl Into<String>) {
+    let prefix = prefix.into();
+    REGISTRY.with_borrow_mut(|registry| {
+        registry.prefix = (!prefix.is_empty()).then_some(prefix);
+    });
+}
+
+/// Apply the tag prefix
+pub(crate) fn prefixed_tag(tag: &str) -> String {
+    REGISTRY.with_borrow(|registry| {
+        registry
+            .prefix
+            .as_ref()
+            .map_or_else(|| tag.to_string(), |prefix| format!("{prefix}-{tag}"))
+    })
+}
+
+/// Check the tag is valid, and not already used in the custom element registry
+pub(crate) fn check_available(
+    tag: &str,
+    custom_element_registry: Option<&CustomElementRegistry>,
+) -> Result<(), RegistrationError> {
+    check_tag(tag)?;
+
+    let registered = REGISTRY.with_borrow(|registry| {
+        registry.components.iter().any(|it| {
+            it.tag == tag && it.custom_element_registry.as_ref() == custom_element_registry
+        })
+    });
+    if registered {
+        return Err(RegistrationError::AlreadyRegistered(tag.to_string()));
+    }
+
+    let custom_elements = custom_element_registry
+        .cloned()
+        .unwrap_or_else(|| window().unwrap_throw().custom_elements());
+    if !custom_elements.get(tag).is_undefined() {
+        return Err(RegistrationError::AlreadyDefined(tag.to_string()));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn add(component: RegisteredComponent) {
+    REGISTRY.with_borrow_mut(|registry| registry.components.push(component));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(tag: &str) -> RegisteredComponent {
+        RegisteredComponent {
+            tag: tag.to_string(),
+            attributes: vec![],
+            properties: vec![],
+            events: vec![],
+            style: InjectedStyle::None,
+            shared_runtime: false,
+            role: None,
+            states: vec![],
+            parts: vec![],
+            css_properties: vec![],
+            lifecycle_events: false,
+            custom_element_registry: None,
+        }
+    }
+
+    #[test]
+    fn should_not_prefix_tag_by_default() {
+        assert_eq!(prefixed_tag("plop-counter"), "plop-counter");
+    }
+
+    #[test]
+    fn should_prefix_tag() {
+        set_tag_prefix("acme");
+        assert_eq!(prefixed_tag("plop-counter"), "acme-plop-counter");
+
+        set_tag_prefix("other");
+        assert_eq!(prefixed_tag("plop-counter"), "other-plop-counter");
+    }
+
+    #[test]
+    fn should_remove_prefix_with_empty_prefix() {
+        set_tag_prefix("acme");
+        set_tag_prefix("");
+        assert_eq!(prefixed_tag("plop-counter"), "plop-counter");
+    }
+
+    #[test]
+    fn should_record_registered_components() {
+        add(component("plop-counter"));
+        add(component("plop-greeting"));
+
+        let tags = registered_components()
+            .into_iter()
+            .map(|component| component.tag)
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec!["plop-counter", "plop-greeting"]);
+    }
+
+    #[test]
+    fn should_reject_invalid_tag() {
+        let result = check_available("plop", None);
+        assert!(matches!(result, Err(RegistrationError::InvalidTag(_))));
+    }
+
+    #[test]
+    fn should_reject_already_registered_tag() {
+        add(component("plop-counter"));
+
+        let result = check_available("plop-counter", None);
+        assert_eq!(
+            result,
+            Err(RegistrationError::AlreadyRegistered(
+                "plop-counter".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_display_registration_error() {
+        let error = RegistrationError::AlreadyRegistered("plop-counter".to_string());
+        assert_eq!(
+            error.to_string(),
+            "the <plop-counter> web component is already registered"
+        );
+        assert!(error.source().is_none());
+
+        let error = RegistrationError::AlreadyDefined("plop-counter".to_string());
+        assert_eq!(
+            error.to_string(),
+            "the <plop-counter> custom element is already defined"
+        );
+    }
+
+    #[test]
+    fn should_keep_invalid_tag_error_as_source() {
+        let Err(tag_error) = check_tag("plop") else {
+            panic!("plop is not a valid custom element name");
+        };
+        let error = RegistrationError::from(tag_error.clone());
+        assert_eq!(error.to_string(), tag_error.to_string());
+        assert!(error.source().is_some());
+    }
+}
@@ -1,18 +1,34 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 
-use dioxus::hooks::UnboundedSender;
-use dioxus::logger::tracing::{debug, warn};
-use dioxus::prelude::LaunchBuilder;
+use dioxus::dioxus_core::VirtualDom;
+use dioxus::logger::tracing::{debug, warn, Level};
 use dioxus::web::Config;
 use futures::channel::oneshot;
+use js_sys::Promise;
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::spawn_local;
-use web_sys::{window, HtmlElement, ShadowRoot};
+use wasm_bindgen_futures::{future_to_promise, spawn_local};
+use web_sys::{window, Element, HtmlElement, ShadowRoot};
 
-use crate::{InjectedStyle, Message, Property, Shared, SharedEventTarget, SharedJsValue};
+use crate::aria::{set_default_role, ElementInternals};
+use crate::builder::DynamicDefinition;
+use crate::context::provide_global_contexts;
+#[cfg(debug_assertions)]
+use crate::devtools::{self, DevtoolsInstance};
+use crate::logging::InstanceTracing;
+use crate::parts::check_parts;
+use crate::shared_runtime::{self, SharedInstance};
+use crate::{
+    dispatch_custom_event, CustomEventOptions, Host, InjectedStyle, Message, MessageChannel,
+    Property, Shared, SharedElementInternals, SharedEventTarget, SharedJsValue,
+};
 
 pub(crate) type DxElBuilder = fn() -> dioxus::dioxus_core::Element;
 
+static NEXT_INSTANCE_ID: AtomicUsize = AtomicUsize::new(0);
+
 /// The Rust component
 #[wasm_bindgen(skip_typescript)]
 pub struct RustComponent {
@@ -20,6 +36,21 @@ pub struct RustComponent {
     pub(crate) properties: Vec<Property>,
     pub(crate) style: InjectedStyle,
     pub(crate) dx_el_builder: DxElBuilder,
+    pub(crate) definition: Option<Arc<DynamicDefinition>>,
+    pub(crate) shared_runtime: bool,
+    pub(crate) tag: String,
+    pub(crate) role: Option<String>,
+    pub(crate) parts: Vec<String>,
+    pub(crate) log_level: Option<Level>,
+    pub(crate) lifecycle_events: bool,
+    pub(crate) style_injected: Cell<bool>,
+}
+
+impl RustComponent {
+    #[cfg(debug_assertions)]
+    fn property_names(&self) -> Vec<String> {
+        self.properties.iter().map(Property::name).collect()
+    }
 }
 
 #[wasm_bindgen]
@@ -34,12 +65,51 @@ impl RustComponent {
         self.properties.clone()
     }
 
+    /// Render all the instances with a single Dioxus application, without shadow DOM
+    #[wasm_bindgen(getter, js_name = "sharedRuntime")]
+    pub fn shared_runtime(&self) -> bool {
+        self.shared_runtime
+    }
+
     #[wasm_bindgen(js_name = "newInstance")]
-    pub fn new_instance(&self, root: &ShadowRoot) -> RustComponentInstance {
+    pub fn new_instance(
+        &self,
+        root: Option<ShadowRoot>,
+        internals: Option<ElementInternals>,
+    ) -> RustComponentInstance {
+        let id = NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed);
+        let tracing = InstanceTracing::new(&self.tag, id, self.log_level);
+        let _guard = tracing.enter();
         debug!(?root, "new instance");
         let window = window().unwrap_throw();
         let document = window.document().unwrap_throw();
-        self.style.inject(&document, root);
+
+        let Some(root) = root else {
+            // Shared runtime, the style goes once in the document head
+            if !self.style_injected.replace(true) {
+                let head = document.head().unwrap_throw();
+                self.style.inject(&document, &head);
+            }
+            return RustComponentInstance {
+                id,
+                attributes: self.attributes(),
+                #[cfg(debug_assertions)]
+                properties: self.property_names(),
+                inner: None,
+                internals,
+                tracing: tracing.clone(),
+                tag: self.tag.clone(),
+                role: self.role.clone(),
+                parts: self.parts.clone(),
+                dx_el_builder: self.dx_el_builder,
+                definition: self.definition.clone(),
+                tx: Arc::default(),
+                pending: Rc::default(),
+                lifecycle_events: self.lifecycle_events,
+                host: None,
+            };
+        };
+        self.style.inject(&document, &root);
 
         // XXX Create an element to attach the dioxus component
         // Dioxus require a `web_sys::Element`, and ShadowRoot is not an Element
@@ -49,51 +119,165 @@ impl RustComponent {
         root.append_child(&inner_elt).unwrap_throw();
 
         RustComponentInstance {
+            id,
             attributes: self.attributes(),
-            inner: inner_elt.into(),
+            #[cfg(debug_assertions)]
+            properties: self.property_names(),
+            inner: Some(inner_elt.into()),
+            internals,
+            tracing: tracing.clone(),
+            tag: self.tag.clone(),
+            role: self.role.clone(),
+            parts: self.parts.clone(),
             dx_el_builder: self.dx_el_builder,
+            definition: self.definition.clone(),
             tx: Arc::default(),
+            pending: Rc::default(),
+            lifecycle_events: self.lifecycle_events,
+            host: None,
         }
     }
 }
 
 #[wasm_bindgen(skip_typescript)]
 pub struct RustComponentInstance {
+    id: usize,
     attributes: Vec<String>,
-    inner: web_sys::Node,
+    /// The property names, for the devtools
+    #[cfg(debug_assertions)]
+    properties: Vec<String>,
+    /// The rendering root, `None` with the shared runtime
+    inner: Option<web_sys::Node>,
+    internals: Option<ElementInternals>,
+    tracing: InstanceTracing,
+    tag: String,
+    /// The default ARIA role
+    role: Option<String>,
+    /// The declared CSS parts
+    parts: Vec<String>,
     dx_el_builder: DxElBuilder,
-    tx: Arc<RwLock<Option<UnboundedSender<Message>>>>,
+    definition: Option<Arc<DynamicDefinition>>,
+    tx: Arc<RwLock<MessageChannel>>,
+    /// The messages waiting for the next microtask
+    pending: Rc<RefCell<Vec<Message>>>,
+    /// Dispatch the `dx-connected`, `dx-rendered`, and `dx-disconnected` events
+    lifecycle_events: bool,
+    /// The connected host
+    host: Option<HtmlElement>,
 }
 
 #[wasm_bindgen]
 impl RustComponentInstance {
     pub fn connect(&mut self, event_target: &HtmlElement) {
+        self.tracing
+            .record_id(event_target.get_attribute("id").as_deref());
+        let _guard = self.tracing.enter();
         debug!(host = ?event_target, "Connect");
+        if let Some(role) = &self.role {
+            set_default_role(event_target, self.internals.as_ref(), role);
+        }
         let ctx = Shared {
             attributes: self.attributes.clone(),
-            event_target: SharedEventTarget(event_target.clone()),
+            host: Host::Element(SharedEventTarget(event_target.clone())),
+            internals: self.internals.clone().map(SharedElementInternals),
             tx: Arc::clone(&self.tx),
         };
+        self.host = Some(event_target.clone());
+        // Skip if poisoned
+        if let Ok(mut tx) = self.tx.write() {
+            tx.connect_host();
+        }
+        if self.lifecycle_events {
+            dispatch_lifecycle_event(event_target, "dx-connected");
+            self.send(rendered_notification(event_target.clone()));
+        }
+
+        let Some(node) = self.inner.clone() else {
+            #[cfg(debug_assertions)]
+            self.connect_devtools(event_target);
+            if let Some(message) = check_parts(&self.tag, event_target, &self.parts) {
+                self.send(message);
+            }
+            shared_runtime::connect(SharedInstance {
+                id: self.id,
+                host: event_target.clone(),
+                shared: ctx,
+                dx_el_builder: self.dx_el_builder,
+                definition: self.definition.clone(),
+            });
+            return;
+        };
+        if let Some(message) = node
+            .dyn_ref::<Element>()
+            .and_then(|root| check_parts(&self.tag, root, &self.parts))
+        {
+            self.send(message);
+        }
+        #[cfg(debug_assertions)]
+        self.connect_devtools(event_target);
+        let config = Config::new().rootnode(node.clone());
+        let mut vdom = VirtualDom::new(self.dx_el_builder);
+        provide_global_contexts(&mut vdom);
+        vdom.insert_any_root_context(Box::new(ctx));
+        if let Some(definition) = &self.definition {
+            vdom.insert_any_root_context(Box::new(Arc::clone(definition)));
+        }
+        #[cfg(feature = "hot-reload")]
+        if let Ok(root) = node.get_root_node().dyn_into::<ShadowRoot>() {
+            crate::hot_reload::register(self.id, &vdom, crate::hot_reload::StyleRoot::Shadow(root));
+        }
+        // The Dioxus application logs are recorded in the instance span
+        let app = async move {
+            Box::pin(dioxus::web::run(vdom, config)).await;
+        };
+        spawn_local(self.tracing.instrument_future(app));
+    }
 
-        let node = self.inner.clone().unchecked_into();
-        let config = Config::new().rootnode(node);
-        LaunchBuilder::web()
-            .with_cfg(config)
-            .with_context(ctx)
-            .launch(self.dx_el_builder);
+    /// Track the instance with the devtools
+    #[cfg(debug_assertions)]
+    fn connect_devtools(&self, host: &HtmlElement) {
+        let instance = DevtoolsInstance::new(
+            self.id,
+            self.tag.clone(),
+            host.clone(),
+            self.attributes.clone(),
+            self.properties.clone(),
+            Arc::clone(&self.tx),
+        );
+        devtools::connect(instance);
     }
 
-    fn send(&mut self, message: Message) {
+    /// Queue the message, the messages of the same microtask are sent together
+    ///
+    /// So `el.a = 1; el.b = 2;` is rendered once, with both values.
+    fn send(&self, message: Message) {
         debug!(?message, "sending message");
+        let first = {
+            let mut pending = self.pending.borrow_mut();
+            pending.push(message);
+            pending.len() == 1
+        };
+        if !first {
+            return;
+        }
+        let pending = Rc::clone(&self.pending);
         let tx = Arc::clone(&self.tx);
-        spawn_local(async move {
-            // Read (skip if poisoned)
-            if let Ok(sender) = tx.try_read() {
-                if let Some(sender) = sender.as_ref() {
-                    let _ = sender.unbounded_send(message);
+        let host = self.host.clone().filter(|_| self.lifecycle_events);
+        spawn_local(self.tracing.instrument_future(async move {
+            let mut messages = pending.take();
+            debug!(count = messages.len(), "sending messages");
+            if let Some(host) = host {
+                if messages.iter().any(is_update) {
+                    messages.push(rendered_notification(host));
                 }
             }
-        });
+            // Send, or queue until connected (skip if poisoned)
+            if let Ok(mut channel) = tx.write() {
+                for message in messages {
+                    channel.send(message);
+                }
+            }
+        }));
     }
 
     #[wasm_bindgen(js_name = "attributeChanged")]
@@ -104,6 +288,7 @@ impl RustComponentInstance {
         old_value: Option<String>,
         new_value: Option<String>,
     ) {
+        let _guard = self.tracing.enter();
         debug!(%name, ?old_value, ?new_value, "attribute changed");
         if old_value != new_value {
             self.send(Message::SetAttribute {
@@ -115,10 +300,14 @@ impl RustComponentInstance {
 
     #[wasm_bindgen(js_name = "getProperty")]
     pub async fn get_property(&mut self, name: String) -> JsValue {
-        debug!(%name, "get property");
-        let (tx, rx) = oneshot::channel();
-        self.send(Message::Get { name, tx });
-        match rx.await {
+        let rx = {
+            let _guard = self.tracing.enter();
+            debug!(%name, "get property");
+            let (tx, rx) = oneshot::channel();
+            self.send(Message::Get { name, tx });
+            rx
+        };
+        match self.tracing.instrument_future(rx).await {
             Ok(SharedJsValue(value)) => value,
             Err(error) => {
                 warn!(?error, "Fail to get property");
@@ -129,17 +318,70 @@ impl RustComponentInstance {
 
     #[wasm_bindgen(js_name = "setProperty")]
     pub fn set_property(&mut self, name: String, value: JsValue) {
+        let _guard = self.tracing.enter();
         debug!(%name, ?value, "set property");
         let value = SharedJsValue(value);
         let message = Message::Set { name, value };
         self.send(message);
     }
 
+    /// A promise resolved with `true` after the render of the previous changes
+    ///
+    /// The value is `false` if the instance is disconnected before the render, or not connected.
+    #[wasm_bindgen(js_name = "updateComplete")]
+    pub fn update_complete(&self) -> Promise {
+        let _guard = self.tracing.enter();
+        debug!("update complete");
+        let (tx, rx) = oneshot::channel();
+        self.send(Message::Flush { tx });
+        future_to_promise(
+            self.tracing
+                .instrument_future(async move { Ok(rx.await.is_ok().into()) }),
+        )
+    }
+
     pub fn disconnect(&mut self) {
+        let _guard = self.tracing.enter();
         debug!("disconnect");
+        if let Some(host) = self.host.take().filter(|_| self.lifecycle_events) {
+            dispatch_lifecycle_event(&host, "dx-disconnected");
+        }
+        #[cfg(debug_assertions)]
+        devtools::disconnect(self.id);
+        #[cfg(feature = "hot-reload")]
+        crate::hot_reload::unregister(self.id);
         // Skip if poisoned
         if let Ok(mut tx) = self.tx.write() {
-            tx.take();
+            tx.disconnect();
+        }
+        if self.inner.is_none() {
+            shared_runtime::disconnect(self.id);
         }
     }
 }
+
+fn is_update(message: &Message) -> bool {
+    matches!(
+        message,
+        Message::SetAttribute { .. } | Message::Set { .. } | Message::SetValue { .. }
+    )
+}
+
+/// Dispatch the `dx-rendered` event after the next render
+fn rendered_notification(host: HtmlElement) -> Message {
+    let (tx, rx) = oneshot::channel();
+    spawn_local(async move {
+        if rx.await.is_ok() {
+            dispatch_lifecycle_event(&host, "dx-rendered");
+        }
+    });
+    Message::Flush { tx }
+}
+
+fn dispatch_lifecycle_event(host: &HtmlElement, event_type: &str) {
+    let options = CustomEventOptions {
+        can_bubble: false,
+        cancelable: false,
+    };
+    dispatch_custom_event(host, event_type, &JsValue::NULL, options);
+}
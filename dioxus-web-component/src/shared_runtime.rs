@@ -0,0 +1,186 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use dioxus::dioxus_core::{DynamicNode, VComponent, VirtualDom};
+use dioxus::hooks::{UnboundedReceiver, UnboundedSender};
+use dioxus::logger::tracing::debug;
+#[allow(clippy::wildcard_imports)]
+use dioxus::prelude::*;
+use dioxus::web::launch::launch_virtual_dom;
+use dioxus::web::{Config, WebEventExt as _};
+use futures::StreamExt as _;
+use wasm_bindgen::UnwrapThrowExt as _;
+use web_sys::{window, HtmlElement};
+
+use crate::builder::DynamicDefinition;
+use crate::context::provide_global_contexts;
+use crate::rust_component::DxElBuilder;
+use crate::Shared;
+
+/// The hot-reload id of the shared runtime, the instances ids come from a counter starting at 0
+#[cfg(feature = "hot-reload")]
+const SHARED_RUNTIME_ID: usize = usize::MAX;
+
+thread_local! {
+    static SHARED_RUNTIME: RefCell<SharedRuntime> = RefCell::default();
+}
+
+/// The single Dioxus application rendering all the instances in shared runtime mode
+///
+/// The application is mounted on the document `<body>`, so the Dioxus event delegation
+/// receives the events of all instances.
+/// Each instance is rendered in a `<div class="dioxus">` moved into the light DOM of its host,
+/// from a hidden `<div>` list item that stays in the `<body>`.
+#[derive(Default)]
+struct SharedRuntime {
+    launched: bool,
+    tx: Option<UnboundedSender<SharedRuntimeMessage>>,
+    pending: Vec<SharedRuntimeMessage>,
+}
+
+enum SharedRuntimeMessage {
+    Connect(SharedInstance),
+    Disconnect(usize),
+}
+
+/// An instance rendered by the shared runtime
+#[derive(Clone)]
+pub(crate) struct SharedInstance {
+    pub(crate) id: usize,
+    pub(crate) host: HtmlElement,
+    pub(crate) shared: Shared,
+    pub(crate) dx_el_builder: DxElBuilder,
+    pub(crate) definition: Option<Arc<DynamicDefinition>>,
+}
+
+impl PartialEq for SharedInstance {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// Render the instance with the shared runtime, the runtime is launched if needed
+pub(crate) fn connect(instance: SharedInstance) {
+    debug!(id = instance.id, "connect to the shared runtime");
+    send(SharedRuntimeMessage::Connect(instance));
+    launch();
+}
+
+/// Remove the instance from the shared runtime
+pub(crate) fn disconnect(id: usize) {
+    debug!(id, "disconnect from the shared runtime");
+    send(SharedRuntimeMessage::Disconnect(id));
+}
+
+fn send(message: SharedRuntimeMessage) {
+    SHARED_RUNTIME.with_borrow_mut(|runtime| {
+        if let Some(tx) = &runtime.tx {
+            let _ = tx.unbounded_send(message);
+        } else {
+            // Wait for the application to be ready
+            runtime.pending.push(message);
+        }
+    });
+}
+
+fn set_tx(tx: UnboundedSender<SharedRuntimeMessage>) {
+    SHARED_RUNTIME.with_borrow_mut(|runtime| {
+        for message in runtime.pending.drain(..) {
+            let _ = tx.unbounded_send(message);
+        }
+        runtime.tx = Some(tx);
+    });
+}
+
+fn launch() {
+    let launched = SHARED_RUNTIME.with_borrow_mut(|runtime| {
+        let launched = runtime.launched;
+        runtime.launched = true;
+        launched
+    });
+    if launched {
+        return;
+    }
+
+    debug!("launch the shared runtime");
+    let document = window().unwrap_throw().document().unwrap_throw();
+    let body = document.body().unwrap_throw();
+    let config = Config::new().rootnode(body.into());
+    let mut vdom = VirtualDom::new(shared_app);
+    provide_global_contexts(&mut vdom);
+    #[cfg(feature = "hot-reload")]
+    crate::hot_reload::register(
+        SHARED_RUNTIME_ID,
+        &vdom,
+        crate::hot_reload::StyleRoot::Document(document),
+    );
+    launch_virtual_dom(vdom, config);
+}
+
+fn shared_app() -> Element {
+    let mut instances = use_signal(Vec::<SharedInstance>::new);
+
+    let coroutine = use_coroutine(
+        move |mut rx: UnboundedReceiver<SharedRuntimeMessage>| async move {
+            while let Some(message) = rx.next().await {
+                match message {
+                    SharedRuntimeMessage::Connect(instance) => instances.write().push(instance),
+                    SharedRuntimeMessage::Disconnect(id) => {
+                        instances.write().retain(|instance| instance.id != id);
+                    }
+                }
+            }
+        },
+    );
+    use_hook(|| set_tx(coroutine.tx()));
+
+    // The list items stay in the `<body>`, so the list edits never use a node moved into a host
+    rsx! {
+        for instance in instances() {
+            div { key: "{instance.id}", hidden: true,
+                SharedInstanceRoot { instance: instance.clone() }
+            }
+        }
+    }
+}
+
+#[component]
+fn SharedInstanceRoot(instance: SharedInstance) -> Element {
+    use_context_provider(|| instance.shared.clone());
+    // The definition never changes for an instance, so the hooks order is stable
+    if let Some(definition) = &instance.definition {
+        use_context_provider(|| Arc::clone(definition));
+    }
+
+    // The rendering root of the instance, moved into the host.
+    // It is the static root of this component, so the Dioxus edits only target its descendants
+    let root = use_hook(|| Rc::new(RefCell::new(None::<web_sys::Element>)));
+    use_drop({
+        let root = Rc::clone(&root);
+        move || {
+            // Dioxus removes the list item, still in the `<body>`
+            if let Some(element) = root.borrow_mut().take() {
+                element.remove();
+            }
+        }
+    });
+
+    let host = instance.host.clone();
+    let component = VComponent::new(instance.dx_el_builder, (), "SharedInstance");
+    let node = DynamicNode::Component(component);
+
+    rsx! {
+        div {
+            class: "dioxus",
+            onmounted: move |event| {
+                // Move the rendered element into the host
+                if let Some(element) = event.data().try_as_web_event() {
+                    host.append_child(&element).unwrap_throw();
+                    root.replace(Some(element));
+                }
+            },
+            {node}
+        }
+    }
+}
@@ -1,5 +1,5 @@
-export function register_web_component(custom_tag, rust_component) {
-	customElements.define(
+export function register_web_component(custom_tag, rust_component, registry) {
+	(registry ?? customElements).define(
 		custom_tag,
 		class extends HTMLElement {
 			static get observedAttributes() {
@@ -8,11 +8,38 @@ export function register_web_component(custom_tag, rust_component) {
 
 			constructor() {
 				super();
-				this.attachShadow({ mode: "open" });
-				const instance = rust_component.newInstance(this.shadowRoot);
+				// The shared runtime renders in the light DOM
+				if (!rust_component.sharedRuntime) {
+					// Scoped registry, see https://github.com/WICG/webcomponents/blob/gh-pages/proposals/Scoped-Custom-Element-Registries.md
+					const options = registry
+						? { mode: "open", customElementRegistry: registry }
+						: { mode: "open" };
+					this.attachShadow(options);
+				}
+				// ElementInternals for the ARIA role and states, see https://developer.mozilla.org/en-US/docs/Web/API/ElementInternals
+				let internals = null;
+				try {
+					internals = this.attachInternals();
+				} catch {
+					// Fallback to the host attributes
+				}
+				const instance = rust_component.newInstance(this.shadowRoot, internals);
 				for (const prop of rust_component.properties) {
 					const { name, readonly } = prop;
+					// Upgrade property, the value set before the element definition shadows the accessors
+					// see https://web.dev/articles/custom-elements-best-practices#make_properties_lazy
+					const hasValue = Object.hasOwn(this, name);
+					const value = hasValue ? this[name] : undefined;
+					if (hasValue) {
+						delete this[name];
+					}
 					if (readonly) {
+						if (hasValue) {
+							console.warn(
+								`<${custom_tag}>: the value set before the upgrade of the readonly '${name}' property is ignored`,
+								value,
+							);
+						}
 						Object.defineProperty(this, name, {
 							get() {
 								return instance.getProperty(name);
@@ -27,11 +54,19 @@ export function register_web_component(custom_tag, rust_component) {
 								instance.setProperty(name, value);
 							},
 						});
+						if (hasValue) {
+							instance.setProperty(name, value);
+						}
 					}
 				}
 				this.instance = instance;
 			}
 
+			// Resolved after the render of the previous changes, like Lit
+			get updateComplete() {
+				return this.instance.updateComplete();
+			}
+
 			attributeChangedCallback(name, oldValue, newValue) {
 				this.instance.attributeChanged(name, oldValue, newValue);
 			}
@@ -46,3 +81,50 @@ export function register_web_component(custom_tag, rust_component) {
 		},
 	);
 }
+
+export function register_deferred(tags, upgrade_when_visible, register) {
+	const selector = tags.map((tag) => `${tag}:not(:defined)`).join(",");
+	const observed = new WeakSet();
+	let done = false;
+
+	const stop = () => {
+		done = true;
+		mutationObserver.disconnect();
+		intersectionObserver?.disconnect();
+	};
+	const load = () => {
+		if (!done) {
+			stop();
+			register();
+		}
+	};
+
+	const intersectionObserver = upgrade_when_visible
+		? new IntersectionObserver((entries) => {
+				if (entries.some((entry) => entry.isIntersecting)) {
+					load();
+				}
+			})
+		: null;
+
+	const check = () => {
+		for (const element of document.querySelectorAll(selector)) {
+			if (!intersectionObserver) {
+				load();
+				return;
+			}
+			if (!observed.has(element)) {
+				observed.add(element);
+				intersectionObserver.observe(element);
+			}
+		}
+	};
+
+	const mutationObserver = new MutationObserver(check);
+	mutationObserver.observe(document, { childList: true, subtree: true });
+
+	// Nothing to do if the tags are defined elsewhere
+	Promise.all(tags.map((tag) => customElements.whenDefined(tag))).then(stop);
+
+	check();
+}
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use wasm_bindgen::UnwrapThrowExt as _;
-use web_sys::{Document, ShadowRoot};
+use web_sys::{Document, Node};
 
 /// Provide style to the web component
 ///
@@ -38,7 +38,7 @@ impl InjectedStyle {
         Self::Stylesheet(Cow::Borrowed(url))
     }
 
-    pub(crate) fn inject(&self, document: &Document, root: &ShadowRoot) {
+    pub(crate) fn inject(&self, document: &Document, root: &Node) {
         match self {
             Self::None => {}
             Self::Css(css) => {
@@ -0,0 +1,290 @@
+use std::any::Any;
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use dioxus::dioxus_core::{Element, NoOpMutations, VirtualDom};
+use dioxus::logger::tracing::debug;
+use futures::FutureExt as _;
+use send_wrapper::SendWrapper;
+
+use crate::{CustomEventOptions, Host, Message, Shared, WebComponentRoot};
+
+/// Avoid an infinite loop when the component keeps rendering
+const MAX_RENDER_ITERATIONS: usize = 1_000;
+
+/// A web component rendered in a plain [`VirtualDom`], without browser
+///
+/// The host element is simulated with a [`TestHost`], so the attributes, the properties
+/// and the custom events can be tested natively, with `cargo test`.
+/// The rendered HTML is available with [`NativeWebComponent::render`].
+///
+/// The component is created with [`NativeWebComponent::of`], from the web component struct
+/// generated by the `#[web_component]` macro, e.g. `CounterWebComponent` for the `Counter` component.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_web_component::{web_component, NativeWebComponent};
+///
+/// #[web_component(tag = "plop-greeting")]
+/// fn Greeting(
+///     #[attribute]
+///     #[property]
+///     name: ReadOnlySignal<String>,
+///     on_greet: EventHandler<String>,
+/// ) -> Element {
+///     use_effect(move || on_greet(name()));
+///     rsx! { p { "Hello {name}!" } }
+/// }
+///
+/// let mut greeting = NativeWebComponent::of::<GreetingWebComponent>().with_attribute("name", "Alice");
+/// assert_eq!(greeting.render(), "<p>Hello Alice!</p>");
+///
+/// greeting.set_property("name", String::from("Bob"));
+/// assert_eq!(greeting.render(), "<p>Hello Bob!</p>");
+/// let greets = greeting.events_detail::<String>("greet");
+/// assert_eq!(greets.last().map(String::as_str), Some("Bob"));
+/// ```
+///
+/// The `use_aria` values are reflected as `aria-*` attributes of the [`TestHost`].
+/// ⚠️ The hooks that require the browser, e.g. `use_custom_state`, or `use_dom_context`,
+/// are not available in this mode.
+pub struct NativeWebComponent {
+    dom: VirtualDom,
+    shared: Shared,
+    host: TestHost,
+    rendered: bool,
+}
+
+impl NativeWebComponent {
+    /// Create a web component generated by the `#[web_component]` macro
+    #[must_use]
+    pub fn of<C>() -> Self
+    where
+        C: WebComponentRoot,
+    {
+        Self::new(C::root)
+    }
+
+    /// Create the component from a root function, e.g. a hand-written web component
+    #[must_use]
+    pub fn new(builder: fn() -> Element) -> Self {
+        let host = TestHost::default();
+        let shared = Shared {
+            attributes: vec![],
+            host: Host::Test(SharedTestHost(host.clone())),
+            internals: None,
+            tx: Arc::default(),
+        };
+        let dom = VirtualDom::new(builder);
+        dom.provide_root_context(shared.clone());
+        Self {
+            dom,
+            shared,
+            host,
+            rendered: false,
+        }
+    }
+
+    /// Set an initial HTML attribute
+    #[must_use]
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Self {
+        self.set_attribute(name, Some(value));
+        self
+    }
+
+    /// The simulated host element
+    #[must_use]
+    pub fn host(&self) -> &TestHost {
+        &self.host
+    }
+
+    /// Set, or remove, an HTML attribute
+    pub fn set_attribute(&mut self, name: &str, value: Option<&str>) {
+        debug!(%name, ?value, "set attribute");
+        self.host.set_attribute(name, value);
+        let name = name.to_string();
+        if !self.shared.attributes.contains(&name) {
+            self.shared.attributes.push(name.clone());
+            // The initial attributes are sent when the component is mounted
+            self.dom.provide_root_context(self.shared.clone());
+        }
+        self.send(Message::SetAttribute {
+            name,
+            value: value.map(ToString::to_string),
+        });
+    }
+
+    /// Set a property with a Rust value
+    ///
+    /// The value type should be the property type.
+    pub fn set_property<T>(&mut self, name: &str, value: T)
+    where
+        T: Send + 'static,
+    {
+        debug!(%name, "set property");
+        self.send(Message::SetValue {
+            name: name.to_string(),
+            value: Box::new(value),
+        });
+    }
+
+    fn send(&self, message: Message) {
+        if let Ok(mut channel) = self.shared.tx.write() {
+            channel.send(message);
+        }
+    }
+
+    /// Run the pending work, then render the component into an HTML string
+    pub fn render(&mut self) -> String {
+        if !self.rendered {
+            self.rendered = true;
+            self.dom.rebuild_in_place();
+        }
+        for _ in 0..MAX_RENDER_ITERATIONS {
+            if self.dom.wait_for_work().now_or_never().is_none() {
+                break;
+            }
+            self.dom.render_immediate(&mut NoOpMutations);
+        }
+        dioxus_ssr::render(&self.dom)
+    }
+
+    /// The custom events dispatched by the component
+    #[must_use]
+    pub fn events(&self) -> Vec<TestEvent> {
+        self.host.events()
+    }
+
+    /// The details of the custom events dispatched by the component with this type
+    #[must_use]
+    pub fn events_detail<T>(&self, event_type: &str) -> Vec<T>
+    where
+        T: Clone + 'static,
+    {
+        self.host
+            .events()
+            .iter()
+            .filter(|event| event.event_type() == event_type)
+            .filter_map(|event| event.detail::<T>().cloned())
+            .collect()
+    }
+}
+
+/// A simulated host element, for the native test mode
+///
+/// See [`NativeWebComponent`].
+#[derive(Clone, Default)]
+pub struct TestHost(Arc<Mutex<TestHostState>>);
+
+#[derive(Default)]
+struct TestHostState {
+    attributes: Vec<(String, String)>,
+    events: Vec<TestEvent>,
+}
+
+impl TestHost {
+    fn state(&self) -> MutexGuard<'_, TestHostState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// An HTML attribute of the host, e.g. a reflected attribute
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.state()
+            .attributes
+            .iter()
+            .find(|(it, _)| it == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub(crate) fn set_attribute(&self, name: &str, value: Option<&str>) {
+        let mut state = self.state();
+        let attributes = &mut state.attributes;
+        attributes.retain(|(it, _)| it != name);
+        if let Some(value) = value {
+            attributes.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    /// The custom events dispatched from the host
+    #[must_use]
+    pub fn events(&self) -> Vec<TestEvent> {
+        self.state().events.clone()
+    }
+
+    /// Forget the dispatched custom events
+    pub fn clear_events(&self) {
+        self.state().events.clear();
+    }
+
+    pub(crate) fn dispatch<T>(&self, event_type: &str, detail: T, options: CustomEventOptions)
+    where
+        T: 'static,
+    {
+        debug!(%event_type, "dispatch test event");
+        self.state().events.push(TestEvent {
+            event_type: event_type.to_string(),
+            detail: Arc::new(SendWrapper::new(detail)),
+            options,
+        });
+    }
+}
+
+impl Debug for TestHost {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        fmt.debug_struct("TestHost")
+            .field("attributes", &state.attributes)
+            .field("events", &state.events)
+            .finish()
+    }
+}
+
+/// A custom event dispatched in the native test mode
+#[derive(Clone)]
+pub struct TestEvent {
+    event_type: String,
+    /// The detail is only available on the thread of the component
+    detail: Arc<dyn Any + Send + Sync>,
+    options: CustomEventOptions,
+}
+
+impl TestEvent {
+    /// The event type
+    #[must_use]
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    /// The event detail, the Rust value before the `JsValue` conversion
+    ///
+    /// The value is `None` with another type, or from another thread than the component thread.
+    #[must_use]
+    pub fn detail<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.detail
+            .downcast_ref::<SendWrapper<T>>()
+            .filter(|detail| detail.valid())
+            .map(|detail| &**detail)
+    }
+
+    /// The event options
+    #[must_use]
+    pub fn options(&self) -> CustomEventOptions {
+        self.options
+    }
+}
+
+impl Debug for TestEvent {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TestEvent")
+            .field("event_type", &self.event_type)
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct SharedTestHost(pub(crate) TestHost);
@@ -0,0 +1,56 @@
+import start from "./pkg/cells.js";
+
+const params = new URLSearchParams(window.location.search);
+const mode = params.get("mode") ?? "isolated";
+const count = Number.parseInt(params.get("count") ?? "1000", 10);
+const tag = mode === "shared" ? "plop-shared-cell" : "plop-cell";
+const columns = 10;
+
+const usedHeap = () => performance.memory?.usedJSHeapSize ?? Number.NaN;
+const formatHeap = (bytes) => `${(bytes / 1024 / 1024).toFixed(1)} MiB`;
+
+// Register the web components
+await start();
+
+const heapBefore = usedHeap();
+const startTime = performance.now();
+
+// Create the cells
+const tbody = document.querySelector("tbody");
+let row;
+for (let index = 0; index < count; index++) {
+	if (index % columns === 0) {
+		row = tbody.insertRow();
+	}
+	const cell = document.createElement(tag);
+	cell.setAttribute("value", `#${index}`);
+	row.insertCell().append(cell);
+}
+
+// Wait until all cells are rendered
+const isRendered = (el) =>
+	(el.shadowRoot ?? el).querySelector(".dioxus button") !== null;
+const cells = [...document.querySelectorAll(tag)];
+const waitRendered = () =>
+	new Promise((resolve) => {
+		const check = () => {
+			if (cells.every(isRendered)) {
+				resolve();
+			} else {
+				requestAnimationFrame(check);
+			}
+		};
+		check();
+	});
+await waitRendered();
+
+const duration = performance.now() - startTime;
+const heap = usedHeap() - heapBefore;
+const result = `${count} <${tag}> rendered in ${duration.toFixed(0)} ms, heap: ${formatHeap(heap)}`;
+document.querySelector("output").textContent = result;
+console.log(result);
+
+// Log 'pick' custom events
+tbody.addEventListener("pick", (evt) => {
+	console.log("pick", evt.detail);
+});
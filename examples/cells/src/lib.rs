@@ -0,0 +1,40 @@
+#![doc = include_str!("../README.md")]
+#![allow(clippy::multiple_crate_versions)]
+
+use dioxus::logger::tracing::Level;
+use dioxus::{logger, prelude::*};
+use dioxus_web_component::web_component;
+use wasm_bindgen::prelude::*;
+
+/// Install (register) the web components
+#[wasm_bindgen(start)]
+pub fn register() {
+    let _ = logger::init(Level::WARN);
+
+    // One Dioxus application per instance
+    register_plop_cell();
+    // One Dioxus application for all instances
+    register_plop_shared_cell();
+}
+
+/// The Dioxus component
+#[component]
+fn Cell(value: String, on_pick: EventHandler<String>) -> Element {
+    let mut clicks = use_signal(|| 0);
+
+    rsx! {
+        button {
+            onclick: move |_| {
+                clicks += 1;
+                on_pick(value.clone());
+            },
+            "{value} ({clicks})"
+        }
+    }
+}
+
+#[web_component(tag = "plop-cell", component = Cell)]
+fn PlopCell(value: String, on_pick: EventHandler<String>);
+
+#[web_component(tag = "plop-shared-cell", component = Cell, shared_runtime = true)]
+fn PlopSharedCell(value: String, on_pick: EventHandler<String>);
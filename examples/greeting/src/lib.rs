@@ -10,6 +10,8 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen(start)]
 pub fn register() {
     let _ = logger::init(Level::INFO);
+    #[cfg(feature = "hot-reload")]
+    dioxus_web_component::connect_devserver("ws://127.0.0.1:8080/_dioxus");
     register_greetings();
 }
 
@@ -24,3 +26,23 @@ fn Greetings(
         p { "Hello {name}!" }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dioxus_web_component::NativeWebComponent;
+
+    use super::*;
+
+    #[test]
+    fn should_greet() {
+        let mut greetings =
+            NativeWebComponent::of::<GreetingsWebComponent>().with_attribute("name", "Alice");
+        assert_eq!(greetings.render(), "<p>Hello Alice!</p>");
+
+        greetings.set_property("name", String::from("Bob"));
+        assert_eq!(greetings.render(), "<p>Hello Bob!</p>");
+
+        greetings.set_attribute("name", None);
+        assert_eq!(greetings.render(), "<p>Hello !</p>");
+    }
+}